@@ -2,12 +2,13 @@ mod build_support;
 use crate::build_support::skia::FinalBuildConfiguration;
 use build_support::{binaries, cargo, git, skia, utils};
 use std::io::Cursor;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::{fs, io};
 
 /// Environment variables used by this build script.
 mod env {
     use crate::build_support::cargo;
+    use std::path::PathBuf;
 
     /// Returns true if the download should be forced. This can be used to test prebuilt binaries
     /// from within a repository build. If this environment variable is not set, binaries
@@ -20,6 +21,19 @@ mod env {
     pub fn force_skia_build() -> bool {
         cargo::env_var("FORCE_SKIA_BUILD").is_some()
     }
+
+    /// The URL to download the prebuilt binaries from. `{tag}` and `{key}` are replaced with the
+    /// tag and the key of the binaries. `file://` URLs are read from the local file system.
+    /// If set, binaries are also downloaded in repository builds.
+    pub fn skia_binaries_url() -> Option<String> {
+        cargo::env_var("SKIA_BINARIES_URL")
+    }
+
+    /// A directory that caches the prebuilt binary archives by their key. Archives found in
+    /// this directory are used without downloading them, and downloaded archives are stored there.
+    pub fn skia_binaries_cache_dir() -> Option<PathBuf> {
+        cargo::env_var("SKIA_BINARIES_CACHE_DIR").map(PathBuf::from)
+    }
 }
 
 const SRC_BINDINGS_RS: &str = "src/bindings.rs";
@@ -42,25 +56,40 @@ fn main() {
     // is the download of prebuilt binaries possible?
     //
 
-    let build_skia = env::force_skia_build() || {
-        if let Some((tag, key)) = should_try_download_binaries(&binaries_config) {
-            println!(
-                "TRYING TO DOWNLOAD AND INSTALL SKIA BINARIES: {}/{}",
-                tag, key
-            );
-            let url = binaries::download_url(tag, key);
-            println!("  FROM: {}", url);
-            if let Err(e) = download_and_install(url, &binaries_config.output_directory) {
-                println!("DOWNLOAD AND INSTALL FAILED: {}", e);
-                true
+    let build_skia = env::force_skia_build()
+        || {
+            if let Some((tag, key)) = should_try_download_binaries(&binaries_config) {
+                println!(
+                    "TRYING TO DOWNLOAD AND INSTALL SKIA BINARIES: {}/{}",
+                    tag,
+                    key.as_deref().unwrap_or("(unknown key)")
+                );
+                if let Some(url) =
+                    binaries::download_url(env::skia_binaries_url(), &tag, key.as_deref())
+                {
+                    println!("  FROM: {}", url);
+                    // without a key, the archive can not be cached.
+                    let cached_archive = key.and_then(|key| {
+                        env::skia_binaries_cache_dir()
+                            .map(|cache_dir| cache_dir.join(binaries::archive_filename(&key)))
+                    });
+                    if let Err(e) =
+                        download_and_install(url, cached_archive, &binaries_config.output_directory)
+                    {
+                        println!("DOWNLOAD AND INSTALL FAILED: {}", e);
+                        true
+                    } else {
+                        println!("DOWNLOAD AND INSTALL SUCCEEDED");
+                        false
+                    }
+                } else {
+                    println!("THE KEY OF THE BINARIES IS UNKNOWN, THE URL MUST NOT CONTAIN {{key}} OUTSIDE OF A GIT REPOSITORY");
+                    true
+                }
             } else {
-                println!("DOWNLOAD AND INSTALL SUCCEEDED");
-                false
+                true
             }
-        } else {
-            true
-        }
-    };
+        };
 
     //
     // full build?
@@ -95,27 +124,59 @@ fn main() {
 }
 
 /// If the binaries should be downloaded, return the tag and the key.
-fn should_try_download_binaries(config: &skia::BinariesConfiguration) -> Option<(String, String)> {
+///
+/// The key is `None` if it can not be determined, because the build does not run inside a
+/// crate or a git repository.
+fn should_try_download_binaries(
+    config: &skia::BinariesConfiguration,
+) -> Option<(String, Option<String>)> {
     let tag = cargo::package_version();
 
     // for testing:
     if env::force_skia_binaries_download() {
         // retrieve the hash from the repository above us.
-        let half_hash = git::half_hash()?;
-        return Some((tag, config.key(&half_hash)));
+        let key = git::half_hash().map(|half_hash| config.key(&half_hash));
+        return Some((tag, key));
     }
 
     // are we building inside a crate?
     if let Ok(ref full_hash) = cargo::crate_repository_hash() {
         let half_hash = git::trim_hash(full_hash);
-        return Some((tag, config.key(&half_hash)));
+        return Some((tag, Some(config.key(&half_hash))));
+    }
+
+    // binaries from a mirror or a local directory?
+    if env::skia_binaries_url().is_some() {
+        let key = git::half_hash().map(|half_hash| config.key(&half_hash));
+        return Some((tag, key));
     }
 
     None
 }
 
-fn download_and_install(url: impl AsRef<str>, output_directory: &Path) -> io::Result<()> {
-    let archive = utils::download(url)?;
+/// Downloads and installs the binaries. If `cached_archive` exists, it is used instead of
+/// downloading the archive, and if not, the downloaded archive is stored there.
+fn download_and_install(
+    url: impl AsRef<str>,
+    cached_archive: Option<PathBuf>,
+    output_directory: &Path,
+) -> io::Result<()> {
+    let archive = match cached_archive {
+        Some(ref cached_archive) if cached_archive.exists() => {
+            println!("USING CACHED ARCHIVE: {}", cached_archive.to_str().unwrap());
+            fs::read(cached_archive)?
+        }
+        _ => {
+            let archive = utils::download(url)?;
+            if let Some(cached_archive) = cached_archive {
+                println!("CACHING ARCHIVE: {}", cached_archive.to_str().unwrap());
+                if let Err(e) = binaries::store_archive(&archive, &cached_archive) {
+                    cargo::warning(format!("Failed to cache the binaries archive: {}", e));
+                }
+            }
+            archive
+        }
+    };
     println!(
         "UNPACKING ARCHIVE INTO: {}",
         output_directory.to_str().unwrap()
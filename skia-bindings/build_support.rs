@@ -8,6 +8,7 @@ pub mod clang;
 pub mod git;
 pub mod ios;
 pub mod llvm;
+pub mod pkg_config;
 pub mod skia;
 pub mod utils;
 pub mod vs;
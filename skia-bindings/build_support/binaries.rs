@@ -119,13 +119,45 @@ pub fn key(repository_short_hash: &str, features: &[impl AsRef<str>], skia_debug
 }
 
 /// Create the download URL for the prebuilt binaries archive.
-pub fn download_url(tag: impl AsRef<str>, key: impl AsRef<str>) -> String {
-    format!(
-        "https://github.com/rust-skia/skia-binaries/releases/download/{}/{}-{}.tar.gz",
-        tag.as_ref(),
-        ARCHIVE_NAME,
-        key.as_ref()
-    )
+///
+/// If `url_template` is set, it is used instead of the URL of the skia-binaries repository
+/// and the placeholders `{tag}` and `{key}` are replaced.
+///
+/// Returns `None` if the URL contains the `{key}` placeholder, but the key is unknown.
+pub fn download_url(
+    url_template: Option<String>,
+    tag: impl AsRef<str>,
+    key: Option<&str>,
+) -> Option<String> {
+    let url = url_template
+        .unwrap_or_else(|| {
+            format!(
+                "https://github.com/rust-skia/skia-binaries/releases/download/{{tag}}/{}",
+                archive_filename("{key}")
+            )
+        })
+        .replace("{tag}", tag.as_ref());
+    match key {
+        Some(key) => Some(url.replace("{key}", key)),
+        None if !url.contains("{key}") => Some(url),
+        None => None,
+    }
+}
+
+/// The filename of the archive for the given key.
+pub fn archive_filename(key: impl AsRef<str>) -> String {
+    format!("{}-{}.tar.gz", ARCHIVE_NAME, key.as_ref())
+}
+
+/// Stores an archive in a local cache, so that subsequent builds do not need to download it.
+pub fn store_archive(archive: &[u8], path: &Path) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // write to a temporary file first, so that concurrent builds never see a partial archive.
+    let temp_path = path.with_extension("partial");
+    fs::write(&temp_path, archive)?;
+    fs::rename(temp_path, path)
 }
 
 pub fn unpack(archive: impl Read, output_directory: &Path) -> io::Result<()> {
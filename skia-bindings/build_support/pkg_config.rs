@@ -0,0 +1,48 @@
+//! Support for resolving the link flags of system libraries with pkg-config.
+
+use crate::build_support::cargo;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// The link flags of one or more pkg-config packages.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct LinkFlags {
+    /// The libraries to link with (`-l`).
+    pub libraries: Vec<String>,
+
+    /// The library search paths (`-L`).
+    pub search_paths: Vec<PathBuf>,
+}
+
+/// Resolves the link flags of the given packages by running `pkg-config --libs`.
+///
+/// Returns `None` if pkg-config is not available or if one of the packages could not be found.
+/// The executable can be overridden with the `PKG_CONFIG` environment variable.
+pub fn link_flags(packages: &[&str]) -> Option<LinkFlags> {
+    let pkg_config = cargo::env_var("PKG_CONFIG").unwrap_or_else(|| "pkg-config".into());
+    let output = Command::new(pkg_config)
+        .arg("--libs")
+        .args(packages)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|flags| parse_link_flags(&flags))
+}
+
+fn parse_link_flags(flags: &str) -> LinkFlags {
+    let mut link_flags = LinkFlags::default();
+    for flag in flags.split_whitespace() {
+        if let Some(library) = flag.strip_prefix("-l") {
+            link_flags.libraries.push(library.into());
+        } else if let Some(search_path) = flag.strip_prefix("-L") {
+            link_flags.search_paths.push(search_path.into());
+        }
+    }
+    link_flags
+}
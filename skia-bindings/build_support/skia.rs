@@ -1,6 +1,6 @@
 //! Full build support for the Skia library, SkiaBindings library and bindings.rs file.
 
-use crate::build_support::{android, binaries, cargo, clang, ios, llvm, vs, xcode};
+use crate::build_support::{android, binaries, cargo, clang, ios, llvm, pkg_config, vs, xcode};
 use cc::Build;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
@@ -19,6 +19,9 @@ mod feature_id {
     pub const GL: &str = "gl";
     pub const VULKAN: &str = "vulkan";
     pub const TEXTLAYOUT: &str = "textlayout";
+    pub const DNG: &str = "dng";
+    pub const TRACING: &str = "tracing";
+    pub const SYSTEM_LIBRARIES: &str = "systemlibs";
 }
 
 /// The defaults for the Skia build configuration.
@@ -34,6 +37,8 @@ impl Default for BuildConfiguration {
         BuildConfiguration {
             on_windows: cargo::host().is_windows(),
             skia_debug,
+            skia_source_dir: cargo::env_var("SKIA_SOURCE_DIR").map(PathBuf::from),
+            ninja_command: cargo::env_var("SKIA_NINJA_COMMAND").map(PathBuf::from),
             keep_inline_functions: true,
             features: Features {
                 gl: cfg!(feature = "gl"),
@@ -41,10 +46,12 @@ impl Default for BuildConfiguration {
                 metal: cfg!(feature = "metal"),
                 text_layout: cfg!(feature = "textlayout"),
                 animation: false,
-                dng: false,
+                dng: cfg!(feature = "dng"),
                 particles: false,
+                tracing: cfg!(feature = "tracing"),
             },
             definitions: Vec::new(),
+            system_libraries: SystemLibrary::from_cargo_env(),
         }
     }
 }
@@ -58,6 +65,13 @@ pub struct BuildConfiguration {
     /// Build Skia in a debug configuration?
     skia_debug: bool,
 
+    /// An already extracted Skia source tree with all its third party dependencies synced.
+    /// If set, the build does not download Skia, depot_tools, or any of Skia's dependencies.
+    skia_source_dir: Option<PathBuf>,
+
+    /// The ninja executable to use. If not set, the one in depot_tools/ is used.
+    ninja_command: Option<PathBuf>,
+
     /// Configure Skia builds to keep inline functions to
     /// prevent linker errors.
     keep_inline_functions: bool,
@@ -67,6 +81,107 @@ pub struct BuildConfiguration {
 
     /// Additional preprocessor definitions that will override predefined ones.
     definitions: Definitions,
+
+    /// The third party libraries that are used from the system instead of building them
+    /// along with Skia.
+    system_libraries: Vec<SystemLibrary>,
+}
+
+/// A third party library that can be used from the system.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SystemLibrary {
+    LibPng,
+    LibJpegTurbo,
+    Zlib,
+    FreeType,
+    HarfBuzz,
+    Icu,
+    Expat,
+}
+
+impl SystemLibrary {
+    pub const ALL: &'static [SystemLibrary] = &[
+        SystemLibrary::LibPng,
+        SystemLibrary::LibJpegTurbo,
+        SystemLibrary::Zlib,
+        SystemLibrary::FreeType,
+        SystemLibrary::HarfBuzz,
+        SystemLibrary::Icu,
+        SystemLibrary::Expat,
+    ];
+
+    /// The name used in the `SKIA_USE_SYSTEM_LIBRARIES` environment variable.
+    pub fn name(self) -> &'static str {
+        match self {
+            SystemLibrary::LibPng => "libpng",
+            SystemLibrary::LibJpegTurbo => "libjpeg-turbo",
+            SystemLibrary::Zlib => "zlib",
+            SystemLibrary::FreeType => "freetype",
+            SystemLibrary::HarfBuzz => "harfbuzz",
+            SystemLibrary::Icu => "icu",
+            SystemLibrary::Expat => "expat",
+        }
+    }
+
+    /// The pkg-config packages of the library.
+    fn pkg_config_packages(self) -> &'static [&'static str] {
+        match self {
+            SystemLibrary::LibPng => &["libpng"],
+            SystemLibrary::LibJpegTurbo => &["libjpeg"],
+            SystemLibrary::Zlib => &["zlib"],
+            SystemLibrary::FreeType => &["freetype2"],
+            SystemLibrary::HarfBuzz => &["harfbuzz", "harfbuzz-subset"],
+            SystemLibrary::Icu => &["icu-uc"],
+            SystemLibrary::Expat => &["expat"],
+        }
+    }
+
+    /// Is the library used by Skia with the given features on the target?
+    fn is_used(self, features: &Features) -> bool {
+        match self {
+            SystemLibrary::HarfBuzz | SystemLibrary::Icu => features.text_layout,
+            // the other targets use the font APIs of their platform.
+            SystemLibrary::FreeType => match cargo::target().system.as_str() {
+                "linux" | "android" => true,
+                _ => false,
+            },
+            _ => true,
+        }
+    }
+
+    /// The system libraries are selected with the feature `use-system-libraries`, which selects
+    /// all of them, or with the environment variable `SKIA_USE_SYSTEM_LIBRARIES`, which can be
+    /// set to `1` for all libraries, or to a comma separated list of library names.
+    fn from_cargo_env() -> Vec<SystemLibrary> {
+        if cfg!(feature = "use-system-libraries") {
+            return Self::ALL.to_vec();
+        }
+
+        match cargo::env_var("SKIA_USE_SYSTEM_LIBRARIES") {
+            None => Vec::new(),
+            Some(ref v) if v == "0" => Vec::new(),
+            Some(ref v) if v == "1" => Self::ALL.to_vec(),
+            Some(v) => v
+                .split(',')
+                .map(|name| name.trim())
+                .filter(|name| !name.is_empty())
+                .map(|name| {
+                    Self::ALL
+                        .iter()
+                        .find(|lib| lib.name() == name)
+                        .copied()
+                        .unwrap_or_else(|| {
+                            let names: Vec<&str> = Self::ALL.iter().map(|l| l.name()).collect();
+                            panic!(
+                                "Unsupported library '{}' in SKIA_USE_SYSTEM_LIBRARIES, supported are: {}",
+                                name,
+                                names.join(", ")
+                            )
+                        })
+                })
+                .collect(),
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -86,11 +201,14 @@ pub struct Features {
     /// Build with animation support (yet unsupported, no wrappers).
     pub animation: bool,
 
-    /// Support DNG file format (currently unsupported because of build errors).
+    /// Support the DNG and camera RAW file formats.
     pub dng: bool,
 
     /// Build the particles module (unsupported, no wrappers).
     pub particles: bool,
+
+    /// Keep Skia's trace events and replace its `SkDebugf()` port.
+    pub tracing: bool,
 }
 
 impl Features {
@@ -114,6 +232,16 @@ pub struct FinalBuildConfiguration {
 
     /// The binding source files to compile.
     pub binding_sources: Vec<PathBuf>,
+
+    /// The directory of the Skia source tree.
+    pub skia_source_dir: PathBuf,
+
+    /// `true` if the Skia source tree and its dependencies are provided and must not be
+    /// resolved or synced.
+    pub skia_source_provided: bool,
+
+    /// The ninja executable.
+    pub ninja_command: PathBuf,
 }
 
 impl FinalBuildConfiguration {
@@ -126,6 +254,14 @@ impl FinalBuildConfiguration {
                 format!("\"{}\"", s)
             }
 
+            let use_system = |lib: SystemLibrary| {
+                if build.system_libraries.contains(&lib) {
+                    yes()
+                } else {
+                    no()
+                }
+            };
+
             let mut args: Vec<(&str, String)> = vec![
                 (
                     "is_official_build",
@@ -134,12 +270,18 @@ impl FinalBuildConfiguration {
                 ("is_debug", if build.skia_debug { yes() } else { no() }),
                 ("skia_enable_gpu", if features.gpu() { yes() } else { no() }),
                 ("skia_use_gl", if features.gl { yes() } else { no() }),
-                ("skia_use_system_libjpeg_turbo", no()),
-                ("skia_use_system_libpng", no()),
+                (
+                    "skia_use_system_libjpeg_turbo",
+                    use_system(SystemLibrary::LibJpegTurbo),
+                ),
+                ("skia_use_system_libpng", use_system(SystemLibrary::LibPng)),
                 ("skia_use_libwebp", no()),
-                ("skia_use_system_zlib", no()),
+                ("skia_use_system_zlib", use_system(SystemLibrary::Zlib)),
                 ("skia_use_xps", no()),
                 ("skia_use_dng_sdk", if features.dng { yes() } else { no() }),
+                // the RAW codec is only built if piex is available, too. It is disabled by default
+                // on some platforms (Windows).
+                ("skia_use_piex", if features.dng { yes() } else { no() }),
                 ("cc", quote("clang")),
                 ("cxx", quote("clang++")),
             ];
@@ -153,6 +295,11 @@ impl FinalBuildConfiguration {
                 args.push(("skia_use_metal", yes()));
             }
 
+            // official builds disable tracing by default.
+            if features.tracing {
+                args.push(("skia_disable_tracing", no()));
+            }
+
             // further flags that limit the components of Skia debug builds.
             if build.skia_debug {
                 args.push(("skia_enable_atlas_text", no()));
@@ -167,10 +314,13 @@ impl FinalBuildConfiguration {
                 args.extend(vec![
                     ("skia_enable_skshaper", yes()),
                     ("skia_use_icu", yes()),
-                    ("skia_use_system_icu", no()),
+                    ("skia_use_system_icu", use_system(SystemLibrary::Icu)),
                     ("skia_use_harfbuzz", yes()),
                     ("skia_pdf_subset_harfbuzz", yes()),
-                    ("skia_use_system_harfbuzz", no()),
+                    (
+                        "skia_use_system_harfbuzz",
+                        use_system(SystemLibrary::HarfBuzz),
+                    ),
                     ("skia_use_sfntly", no()),
                     ("skia_enable_skparagraph", yes()),
                     // note: currently, tests need to be enabled, because modules/skparagraph
@@ -217,7 +367,10 @@ impl FinalBuildConfiguration {
                     // TODO: make API-level configurable?
                     args.push(("ndk_api", android::API_LEVEL.into()));
                     args.push(("target_cpu", quote(clang::target_arch(arch))));
-                    args.push(("skia_use_system_freetype2", no()));
+                    args.push((
+                        "skia_use_system_freetype2",
+                        use_system(SystemLibrary::FreeType),
+                    ));
                     args.push(("skia_enable_fontmgr_android", yes()));
                     // Enabling fontmgr_android implicitly enables expat.
                     // We make this explicit to avoid relying on an expat installed
@@ -228,12 +381,20 @@ impl FinalBuildConfiguration {
                     args.push(("target_os", quote("ios")));
                     args.push(("target_cpu", quote(clang::target_arch(arch))));
                 }
-                _ => {}
+                _ => {
+                    // otherwise, Skia's default for FreeType is kept. FreeType is not used on
+                    // macOS, iOS, and Windows, see `SystemLibrary::is_used()`.
+                    if SystemLibrary::FreeType.is_used(features)
+                        && build.system_libraries.contains(&SystemLibrary::FreeType)
+                    {
+                        args.push(("skia_use_system_freetype2", yes()));
+                    }
+                }
             }
 
             if use_expat {
                 args.push(("skia_use_expat", yes()));
-                args.push(("skia_use_system_expat", no()));
+                args.push(("skia_use_system_expat", use_system(SystemLibrary::Expat)));
             } else {
                 args.push(("skia_use_expat", no()));
             }
@@ -282,14 +443,44 @@ impl FinalBuildConfiguration {
                 sources.extend(vec!["src/shaper.cpp".into(), "src/paragraph.cpp".into()]);
             }
             sources.push("src/svg.cpp".into());
+            if features.tracing {
+                sources.push("src/tracing.cpp".into());
+            }
             sources
         };
 
+        let current_dir = env::current_dir().unwrap();
+
+        let skia_source_dir = current_dir.join(
+            build
+                .skia_source_dir
+                .clone()
+                .unwrap_or_else(|| "skia".into()),
+        );
+
+        let ninja_command = build.ninja_command.clone().unwrap_or_else(|| {
+            let ninja = if build.on_windows {
+                "ninja.exe"
+            } else {
+                "ninja"
+            };
+            let depot_tools = current_dir.join("depot_tools");
+            if build.skia_source_dir.is_some() && !depot_tools.exists() {
+                // expect ninja in the PATH, downloading depot_tools would need network access.
+                ninja.into()
+            } else {
+                depot_tools.join(ninja)
+            }
+        });
+
         FinalBuildConfiguration {
             gn_args,
             ninja_files,
             definitions: build.definitions.clone(),
             binding_sources,
+            skia_source_dir,
+            skia_source_provided: build.skia_source_dir.is_some(),
+            ninja_command,
         }
     }
 }
@@ -313,6 +504,9 @@ pub struct BinariesConfiguration {
     /// The TARGET specific link libraries we need to inform cargo about.
     pub link_libraries: Vec<String>,
 
+    /// Additional search paths for the link libraries, for example of system libraries.
+    pub link_search_paths: Vec<PathBuf>,
+
     /// The static Skia libraries skia-bindings provides and dependent projects need to link with.
     pub built_libraries: Vec<String>,
 
@@ -344,9 +538,17 @@ impl BinariesConfiguration {
         if features.vulkan {
             feature_ids.push(feature_id::VULKAN);
         }
+        if features.dng {
+            feature_ids.push(feature_id::DNG);
+        }
+        if features.tracing {
+            feature_ids.push(feature_id::TRACING);
+        }
         if features.text_layout {
             feature_ids.push(feature_id::TEXTLAYOUT);
-            additional_files.push(ICUDTL_DAT.into());
+            if !build.system_libraries.contains(&SystemLibrary::Icu) {
+                additional_files.push(ICUDTL_DAT.into());
+            }
             built_libraries.push(lib::SKPARAGRAPH.into());
             built_libraries.push(lib::SKSHAPER.into());
         }
@@ -385,6 +587,38 @@ impl BinariesConfiguration {
             _ => panic!("unsupported target: {:?}", cargo::target()),
         };
 
+        // prebuilt binaries are never built with system libraries.
+        if !build.system_libraries.is_empty() {
+            feature_ids.push(feature_id::SYSTEM_LIBRARIES);
+        }
+
+        let mut link_libraries: Vec<String> = link_libraries
+            .into_iter()
+            .map(|lib| lib.to_string())
+            .collect();
+        let mut link_search_paths: Vec<PathBuf> = Vec::new();
+
+        for lib in build
+            .system_libraries
+            .iter()
+            .filter(|lib| lib.is_used(features))
+        {
+            // the library was explicitly requested, so don't guess its link flags.
+            let flags = pkg_config::link_flags(lib.pkg_config_packages()).unwrap_or_else(|| {
+                panic!(
+                    "pkg-config failed to resolve {:?}, which is required to link with the system library {}. Make sure that pkg-config and the library's development files are installed, or remove the library from SKIA_USE_SYSTEM_LIBRARIES",
+                    lib.pkg_config_packages(),
+                    lib.name()
+                )
+            });
+            link_libraries.extend(flags.libraries);
+            link_search_paths.extend(flags.search_paths);
+        }
+
+        // system libraries may already be part of the target's link libraries.
+        dedup_in_order(&mut link_libraries);
+        dedup_in_order(&mut link_search_paths);
+
         let output_directory = cargo::output_directory()
             .join(SKIA_OUTPUT_DIR)
             .to_str()
@@ -397,10 +631,8 @@ impl BinariesConfiguration {
         BinariesConfiguration {
             feature_ids: feature_ids.into_iter().map(|f| f.to_string()).collect(),
             output_directory,
-            link_libraries: link_libraries
-                .into_iter()
-                .map(|lib| lib.to_string())
-                .collect(),
+            link_libraries,
+            link_search_paths,
             built_libraries,
             additional_files,
             skia_debug: build.skia_debug,
@@ -415,6 +647,10 @@ impl BinariesConfiguration {
             self.output_directory.to_str().unwrap()
         );
 
+        for path in &self.link_search_paths {
+            println!("cargo:rustc-link-search={}", path.to_str().unwrap());
+        }
+
         // On Linux, the order is significant, first the static libraries we built, and then
         // the system libraries.
 
@@ -430,25 +666,46 @@ impl BinariesConfiguration {
     }
 }
 
+/// Removes duplicates and keeps the first occurrence of each element.
+fn dedup_in_order<T: PartialEq + Clone>(v: &mut Vec<T>) {
+    let mut unique: Vec<T> = Vec::new();
+    for e in v.iter() {
+        if !unique.contains(e) {
+            unique.push(e.clone());
+        }
+    }
+    *v = unique;
+}
+
 /// The full build of Skia, SkiaBindings, and the generation of bindings.rs.
 pub fn build(build: &FinalBuildConfiguration, config: &BinariesConfiguration) {
-    prerequisites::resolve_dependencies();
-
-    // call Skia's git-sync-deps
+    if build.skia_source_provided {
+        println!(
+            "USING THE SKIA SOURCE TREE AT: {}",
+            build.skia_source_dir.to_str().unwrap()
+        );
+    } else {
+        prerequisites::resolve_dependencies();
+    }
 
+    // probe for Python 2 not before the source tree is available.
     let python2 = &prerequisites::locate_python2_cmd();
     println!("Python 2 found: {:?}", python2);
 
-    assert!(
-        Command::new(python2)
-            .arg("skia/tools/git-sync-deps")
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()
-            .unwrap()
-            .success(),
-        "`skia/tools/git-sync-deps` failed"
-    );
+    if !build.skia_source_provided {
+        // call Skia's git-sync-deps
+
+        assert!(
+            Command::new(python2)
+                .arg(build.skia_source_dir.join("tools").join("git-sync-deps"))
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit())
+                .status()
+                .unwrap()
+                .success(),
+            "`skia/tools/git-sync-deps` failed"
+        );
+    }
 
     // configure Skia
 
@@ -459,8 +716,7 @@ pub fn build(build: &FinalBuildConfiguration, config: &BinariesConfiguration) {
         .collect::<Vec<String>>()
         .join(" ");
 
-    let current_dir = env::current_dir().unwrap();
-    let gn_command = current_dir.join("skia").join("bin").join("gn");
+    let gn_command = build.skia_source_dir.join("bin").join("gn");
 
     let output_directory_str = config.output_directory.to_str().unwrap();
 
@@ -474,7 +730,7 @@ pub fn build(build: &FinalBuildConfiguration, config: &BinariesConfiguration) {
             &format!("--args={}", gn_args),
         ])
         .envs(env::vars())
-        .current_dir(PathBuf::from("./skia"))
+        .current_dir(&build.skia_source_dir)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .output()
@@ -486,15 +742,8 @@ pub fn build(build: &FinalBuildConfiguration, config: &BinariesConfiguration) {
 
     // build Skia
 
-    let on_windows = cfg!(windows);
-
-    let ninja_command =
-        current_dir
-            .join("depot_tools")
-            .join(if on_windows { "ninja.exe" } else { "ninja" });
-
-    let ninja_status = Command::new(ninja_command)
-        .current_dir(PathBuf::from("./skia"))
+    let ninja_status = Command::new(&build.ninja_command)
+        .current_dir(&build.skia_source_dir)
         .args(&["-C", output_directory_str])
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
@@ -507,10 +756,10 @@ pub fn build(build: &FinalBuildConfiguration, config: &BinariesConfiguration) {
         "`ninja` returned an error, please check the output for details."
     );
 
-    bindgen_gen(build, &current_dir, &config.output_directory)
+    bindgen_gen(build, &config.output_directory)
 }
 
-fn bindgen_gen(build: &FinalBuildConfiguration, current_dir: &Path, output_directory: &Path) {
+fn bindgen_gen(build: &FinalBuildConfiguration, output_directory: &Path) {
     let mut builder = bindgen::Builder::default()
         .generate_comments(false)
         .layout_tests(true)
@@ -577,9 +826,7 @@ fn bindgen_gen(build: &FinalBuildConfiguration, current_dir: &Path, output_direc
         builder = builder.header(source);
     }
 
-    // TODO: may put the include paths into the FinalBuildConfiguration?
-
-    let include_path = current_dir.join("skia");
+    let include_path = &build.skia_source_dir;
     cargo::rerun_if_changed(include_path.join("include"));
 
     builder = builder.clang_arg(format!("-I{}", include_path.display()));
@@ -894,6 +1141,8 @@ const ENUM_TABLE: &[EnumEntry] = &[
     ("Mode", rewrite::k_xxx),
     // SkTypeface_SerializeBehavior
     ("SerializeBehavior", rewrite::k_xxx),
+    // SkTraceMemoryDump_LevelOfDetail
+    ("LevelOfDetail", rewrite::k_xxx_name),
     // SkVertices_VertexMode
     ("VertexMode", rewrite::k_xxx_name),
     // SkYUVAIndex_Index
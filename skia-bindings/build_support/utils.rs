@@ -1,8 +1,15 @@
 use curl::easy::Easy;
-use std::io;
+use std::path::PathBuf;
+use std::{fs, io};
 
 /// Download a file from the given URL and return the data.
+///
+/// `file://` URLs are read directly from the local file system.
 pub fn download(url: impl AsRef<str>) -> io::Result<Vec<u8>> {
+    if let Some(path) = file_url_path(url.as_ref()) {
+        return fs::read(path);
+    }
+
     let mut data = Vec::new();
     let mut handle = Easy::new();
     handle.url(url.as_ref())?;
@@ -23,3 +30,19 @@ pub fn download(url: impl AsRef<str>) -> io::Result<Vec<u8>> {
         Ok(()) => Ok(data),
     }
 }
+
+/// Returns the local path of a `file://` URL, or `None` if `url` is not a `file://` URL.
+///
+/// On Windows, the slash in front of the drive letter is removed, so `file:///C:/skia` results
+/// in `C:/skia`.
+fn file_url_path(url: &str) -> Option<PathBuf> {
+    let path = url.strip_prefix("file://")?;
+    let bytes = path.as_bytes();
+    let has_drive_letter =
+        bytes.len() >= 3 && bytes[0] == b'/' && bytes[1].is_ascii_alphabetic() && bytes[2] == b':';
+    if has_drive_letter {
+        Some(PathBuf::from(&path[1..]))
+    } else {
+        Some(PathBuf::from(path))
+    }
+}
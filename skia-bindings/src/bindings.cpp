@@ -7,6 +7,7 @@
 #include "include/codec/SkCodec.h"
 // core/
 #include "include/core/SkAnnotation.h"
+#include "include/core/SkBBHFactory.h"
 #include "include/core/SkBlendMode.h"
 #include "include/core/SkCanvas.h"
 #include "include/core/SkColor.h"
@@ -30,6 +31,7 @@
 #include "include/core/SkImageGenerator.h"
 #include "include/core/SkImageInfo.h"
 #include "include/core/SkMaskFilter.h"
+#include "include/core/SkOverdrawCanvas.h"
 #include "include/core/SkPaint.h"
 #include "include/core/SkPath.h"
 #include "include/core/SkPathMeasure.h"
@@ -44,6 +46,7 @@
 #include "include/core/SkRegion.h"
 #include "include/core/SkRRect.h"
 #include "include/core/SkRSXform.h"
+#include "include/core/SkSerialProcs.h"
 #include "include/core/SkStream.h"
 #include "include/core/SkStrokeRec.h"
 #include "include/core/SkSurface.h"
@@ -106,11 +109,21 @@
 #include "include/utils/Sk3D.h"
 #include "include/utils/SkCamera.h"
 #include "include/utils/SkInterpolator.h"
+#include "include/utils/SkNWayCanvas.h"
+#include "include/utils/SkNoDrawCanvas.h"
 #include "include/utils/SkNullCanvas.h"
+#include "include/utils/SkPaintFilterCanvas.h"
 #include "include/utils/SkParsePath.h"
 #include "include/utils/SkShadowUtils.h"
 #include "include/utils/SkTextUtils.h"
 
+#include "src/core/SkBBoxHierarchy.h"
+#include "src/core/SkBigPicture.h"
+#include "src/core/SkPicturePriv.h"
+#include "src/core/SkStrikeSpec.h"
+#include "src/core/SkDrawShadowInfo.h"
+#include "src/utils/SkMultiPictureDocument.h"
+
 //
 // codec/SkCodec.h
 //
@@ -209,6 +222,67 @@ extern "C" const SkSurfaceProps* C_SkSurface_props(const SkSurface* self) {
     return &self->props();
 }
 
+extern "C" {
+    struct RustAsyncReadResult {
+        int count;
+        const void* data[3];
+        size_t rowBytes[3];
+    };
+
+    // result is null if the read failed.
+    typedef void (*RustReadPixelsCallback)(void* ctx, const RustAsyncReadResult* result);
+}
+
+struct RustReadPixelsContext {
+    void* ctx;
+    RustReadPixelsCallback callback;
+
+    static void Callback(SkSurface::ReadPixelsContext context, std::unique_ptr<const SkSurface::AsyncReadResult> result) {
+        std::unique_ptr<RustReadPixelsContext> rustContext(static_cast<RustReadPixelsContext*>(context));
+        if (!result) {
+            rustContext->callback(rustContext->ctx, nullptr);
+            return;
+        }
+        RustAsyncReadResult rustResult = {};
+        rustResult.count = std::min(result->count(), 3);
+        for (int i = 0; i != rustResult.count; ++i) {
+            rustResult.data[i] = result->data(i);
+            rustResult.rowBytes[i] = result->rowBytes(i);
+        }
+        rustContext->callback(rustContext->ctx, &rustResult);
+    }
+};
+
+extern "C" void C_SkSurface_asyncRescaleAndReadPixels(
+        SkSurface* self,
+        const SkImageInfo* info,
+        const SkIRect* srcRect,
+        bool linearGamma,
+        SkFilterQuality rescaleQuality,
+        void* ctx, RustReadPixelsCallback callback) {
+    self->asyncRescaleAndReadPixels(
+            *info, *srcRect,
+            linearGamma ? SkSurface::RescaleGamma::kLinear : SkSurface::RescaleGamma::kSrc,
+            rescaleQuality,
+            RustReadPixelsContext::Callback, new RustReadPixelsContext{ctx, callback});
+}
+
+extern "C" void C_SkSurface_asyncRescaleAndReadPixelsYUV420(
+        SkSurface* self,
+        SkYUVColorSpace yuvColorSpace,
+        SkColorSpace* dstColorSpace,
+        const SkIRect* srcRect,
+        const SkISize* dstSize,
+        bool linearGamma,
+        SkFilterQuality rescaleQuality,
+        void* ctx, RustReadPixelsCallback callback) {
+    self->asyncRescaleAndReadPixelsYUV420(
+            yuvColorSpace, sp(dstColorSpace), *srcRect, *dstSize,
+            linearGamma ? SkSurface::RescaleGamma::kLinear : SkSurface::RescaleGamma::kSrc,
+            rescaleQuality,
+            RustReadPixelsContext::Callback, new RustReadPixelsContext{ctx, callback});
+}
+
 //
 // core/SkSurfaceCharacterization.h
 //
@@ -246,6 +320,12 @@ extern "C" SkImage* C_SkImage_MakeRasterData(const SkImageInfo* info, SkData* pi
     return SkImage::MakeRasterData(*info, sp(pixels), rowBytes).release();
 }
 
+extern "C" SkImage* C_SkImage_MakeFromRaster(
+        const SkImageInfo* info, const void* pixels, size_t rowBytes,
+        SkImage::RasterReleaseProc releaseProc, SkImage::ReleaseContext releaseContext) {
+    return SkImage::MakeFromRaster(SkPixmap(*info, pixels, rowBytes), releaseProc, releaseContext).release();
+}
+
 extern "C" SkImage* C_SkImage_MakeFromBitmap(const SkBitmap* bitmap) {
     return SkImage::MakeFromBitmap(*bitmap).release();
 }
@@ -360,6 +440,10 @@ extern "C" SkData* C_SkData_MakeWithoutCopy(const void* data, size_t length) {
     return SkData::MakeWithoutCopy(data, length).release();
 }
 
+extern "C" SkData* C_SkData_MakeWithProc(const void* ptr, size_t length, SkData::ReleaseProc proc, void* ctx) {
+    return SkData::MakeWithProc(ptr, length, proc, ctx).release();
+}
+
 extern "C" SkData* C_SkData_MakeEmpty() {
     return SkData::MakeEmpty().release();
 }
@@ -586,6 +670,14 @@ extern "C" void C_SkAutoCanvasRestore_restore(SkAutoCanvasRestore* self) {
     self->restore();
 }
 
+//
+// core/SkOverdrawCanvas.h
+//
+
+extern "C" SkCanvas* C_SkOverdrawCanvas_new(SkCanvas* canvas) {
+    return new SkOverdrawCanvas(canvas);
+}
+
 //
 // core/SkImageInfo.h
 //
@@ -818,6 +910,82 @@ extern "C" SkShader* C_SkBitmap_makeShader(const SkBitmap* self, SkTileMode tmx,
     return self->makeShader(tmx, tmy, localMatrix).release();
 }
 
+//
+// core/SkSerialProcs.h
+//
+
+extern "C" {
+
+struct RustSerialProcs {
+    void* ctx;
+    SkData* (*pictureProc)(void* ctx, SkPicture* picture);
+    SkData* (*imageProc)(void* ctx, SkImage* image);
+    SkData* (*typefaceProc)(void* ctx, SkTypeface* typeface);
+};
+
+struct RustDeserialProcs {
+    void* ctx;
+    SkPicture* (*pictureProc)(void* ctx, const void* data, size_t length);
+    SkImage* (*imageProc)(void* ctx, const void* data, size_t length);
+    SkTypeface* (*typefaceProc)(void* ctx, const void* data, size_t length);
+};
+
+}
+
+static SkSerialProcs toSkSerialProcs(const RustSerialProcs* rustProcs) {
+    SkSerialProcs procs;
+    void* ctx = const_cast<RustSerialProcs*>(rustProcs);
+    if (rustProcs->pictureProc) {
+        procs.fPictureProc = [](SkPicture* picture, void* ctx) {
+            auto procs = static_cast<RustSerialProcs*>(ctx);
+            return sp(procs->pictureProc(procs->ctx, picture));
+        };
+        procs.fPictureCtx = ctx;
+    }
+    if (rustProcs->imageProc) {
+        procs.fImageProc = [](SkImage* image, void* ctx) {
+            auto procs = static_cast<RustSerialProcs*>(ctx);
+            return sp(procs->imageProc(procs->ctx, image));
+        };
+        procs.fImageCtx = ctx;
+    }
+    if (rustProcs->typefaceProc) {
+        procs.fTypefaceProc = [](SkTypeface* typeface, void* ctx) {
+            auto procs = static_cast<RustSerialProcs*>(ctx);
+            return sp(procs->typefaceProc(procs->ctx, typeface));
+        };
+        procs.fTypefaceCtx = ctx;
+    }
+    return procs;
+}
+
+static SkDeserialProcs toSkDeserialProcs(const RustDeserialProcs* rustProcs) {
+    SkDeserialProcs procs;
+    void* ctx = const_cast<RustDeserialProcs*>(rustProcs);
+    if (rustProcs->pictureProc) {
+        procs.fPictureProc = [](const void* data, size_t length, void* ctx) {
+            auto procs = static_cast<RustDeserialProcs*>(ctx);
+            return sp(procs->pictureProc(procs->ctx, data, length));
+        };
+        procs.fPictureCtx = ctx;
+    }
+    if (rustProcs->imageProc) {
+        procs.fImageProc = [](const void* data, size_t length, void* ctx) {
+            auto procs = static_cast<RustDeserialProcs*>(ctx);
+            return sp(procs->imageProc(procs->ctx, data, length));
+        };
+        procs.fImageCtx = ctx;
+    }
+    if (rustProcs->typefaceProc) {
+        procs.fTypefaceProc = [](const void* data, size_t length, void* ctx) {
+            auto procs = static_cast<RustDeserialProcs*>(ctx);
+            return sp(procs->typefaceProc(procs->ctx, data, length));
+        };
+        procs.fTypefaceCtx = ctx;
+    }
+    return procs;
+}
+
 //
 // core/SkPicture.h
 //
@@ -830,10 +998,20 @@ extern "C" SkPicture* C_SkPicture_MakeFromData2(const void* data, size_t size) {
     return SkPicture::MakeFromData(data, size).release();
 }
 
+extern "C" SkPicture* C_SkPicture_MakeFromDataWithProcs(const void* data, size_t size, const RustDeserialProcs* procs) {
+    auto skProcs = toSkDeserialProcs(procs);
+    return SkPicture::MakeFromData(data, size, &skProcs).release();
+}
+
 extern "C" SkData* C_SkPicture_serialize(const SkPicture* self) {
     return self->serialize().release();
 }
 
+extern "C" SkData* C_SkPicture_serializeWithProcs(const SkPicture* self, const RustSerialProcs* procs) {
+    auto skProcs = toSkSerialProcs(procs);
+    return self->serialize(&skProcs).release();
+}
+
 extern "C" SkPicture* C_SkPicture_MakePlaceholder(const SkRect& cull) {
     return SkPicture::MakePlaceholder(cull).release();
 }
@@ -842,6 +1020,42 @@ extern "C" void C_SkPicture_playback(const SkPicture* self, SkCanvas* canvas) {
     self->playback(canvas);
 }
 
+typedef bool (*AbortCallbackFn)(void* ctx);
+
+class RustAbortCallback: public SkPicture::AbortCallback {
+public:
+    RustAbortCallback(void* ctx, AbortCallbackFn abortFn)
+    : _ctx(ctx), _abortFn(abortFn) {
+    }
+
+    bool abort() override {
+        return _abortFn(_ctx);
+    }
+
+private:
+    void* _ctx;
+    AbortCallbackFn _abortFn;
+};
+
+extern "C" void C_SkPicture_playbackWithAbortCallback(const SkPicture* self, SkCanvas* canvas, void* ctx, AbortCallbackFn abortFn) {
+    RustAbortCallback callback(ctx, abortFn);
+    self->playback(canvas, &callback);
+}
+
+extern "C" bool C_SkPicture_partialPlayback(const SkPicture* self, SkCanvas* canvas, int start, int stop) {
+    auto bigPicture = SkPicturePriv::AsSkBigPicture(sk_ref_sp(self));
+    if (bigPicture) {
+        bigPicture->partialPlayback(canvas, start, stop, canvas->getTotalMatrix());
+        return true;
+    }
+    // pictures that are not backed by a SkRecord contain at most one op.
+    if (start == 0 && stop >= self->approximateOpCount()) {
+        self->playback(canvas);
+        return true;
+    }
+    return false;
+}
+
 extern "C" SkRect C_SkPicture_cullRect(const SkPicture* self) {
     return self->cullRect();
 }
@@ -854,6 +1068,10 @@ extern "C" int C_SkPicture_approximateOpCount(const SkPicture* self) {
     return self->approximateOpCount();
 }
 
+extern "C" int C_SkPicture_approximateOpCountNested(const SkPicture* self, bool nested) {
+    return self->approximateOpCount(nested);
+}
+
 // note: returning size_t produces a linker error.
 extern "C" void C_SkPicture_approximateBytesUsed(const SkPicture* self, size_t* out) {
     *out = self->approximateBytesUsed();
@@ -1096,6 +1314,28 @@ extern "C" SkData* C_SkFlattenable_serialize(const SkFlattenable* self) {
     return self->serialize().release();
 }
 
+extern "C" SkFlattenable::Type C_SkFlattenable_getFlattenableType(const SkFlattenable* self) {
+    return self->getFlattenableType();
+}
+
+// Factories are passed as void* to avoid exposing SkReadBuffer and the sk_sp return type.
+
+extern "C" void* C_SkFlattenable_getFactory(const SkFlattenable* self) {
+    return reinterpret_cast<void*>(self->getFactory());
+}
+
+extern "C" void* C_SkFlattenable_NameToFactory(const char* name) {
+    return reinterpret_cast<void*>(SkFlattenable::NameToFactory(name));
+}
+
+extern "C" const char* C_SkFlattenable_FactoryToName(void* factory) {
+    return SkFlattenable::FactoryToName(reinterpret_cast<SkFlattenable::Factory>(factory));
+}
+
+extern "C" void C_SkFlattenable_Register(const char* name, void* factory) {
+    SkFlattenable::Register(name, reinterpret_cast<SkFlattenable::Factory>(factory));
+}
+
 //
 // core/SkFont.h
 //
@@ -1140,6 +1380,86 @@ extern "C" void C_SkFont_destruct(SkFont* self) {
     self->~SkFont();
 }
 
+extern "C" {
+    // bounds are relative to the glyph's origin.
+    struct RustGlyphImage {
+        SkIRect bounds;
+        SkColorType colorType;
+        const void* pixels;
+        size_t rowBytes;
+    };
+
+    // image is null if the glyph has no image.
+    typedef void (*RustGlyphImageVisitor)(void* ctx, int index, const RustGlyphImage* image);
+}
+
+// Renders the glyphs with the scaler of the mask strike that a raster device would use.
+extern "C" void C_SkFont_visitGlyphImages(
+        const SkFont* self, const SkPaint* paint,
+        const SkGlyphID glyphs[], int count, SkVector subpixelOffset,
+        void* ctx, RustGlyphImageVisitor visitor) {
+    SkPaint defaultPaint;
+    SkSurfaceProps props(0, kUnknown_SkPixelGeometry);
+    auto strikeSpec = SkStrikeSpec::MakeMask(
+            *self, paint ? *paint : defaultPaint, props,
+            SkScalerContextFlags::kFakeGammaAndBoostContrast, SkMatrix::I());
+    // the images are owned by the strike, which lives as long as images.
+    SkBulkGlyphMetricsAndImages images{strikeSpec};
+
+    // without subpixel positioning, the glyphs are drawn at whole pixels.
+    SkFixed x = self->isSubpixel() ? SkScalarToFixed(subpixelOffset.x()) : 0;
+    SkFixed y = self->isSubpixel() ? SkScalarToFixed(subpixelOffset.y()) : 0;
+
+    for (int i = 0; i < count; ++i) {
+        const SkGlyph* glyph = images.glyph(SkPackedGlyphID(glyphs[i], x, y));
+        if (glyph->isEmpty() || !glyph->image()) {
+            visitor(ctx, i, nullptr);
+            continue;
+        }
+
+        RustGlyphImage image;
+        image.bounds = SkIRect::MakeXYWH(glyph->left(), glyph->top(), glyph->width(), glyph->height());
+        image.pixels = glyph->image();
+        image.rowBytes = glyph->rowBytes();
+
+        std::vector<uint8_t> expanded;
+        switch (glyph->maskFormat()) {
+            case SkMask::kBW_Format: {
+                // 1 bit per pixel, expanded to 8 bit coverage.
+                auto width = glyph->width();
+                auto height = glyph->height();
+                auto bits = static_cast<const uint8_t*>(glyph->image());
+                expanded.resize(width * height);
+                for (int row = 0; row < height; ++row) {
+                    for (int column = 0; column < width; ++column) {
+                        auto bit = bits[row * glyph->rowBytes() + column / 8] & (0x80 >> (column % 8));
+                        expanded[row * width + column] = bit ? 0xff : 0;
+                    }
+                }
+                image.colorType = kAlpha_8_SkColorType;
+                image.pixels = expanded.data();
+                image.rowBytes = width;
+                break;
+            }
+            // the first plane of 3D masks is the coverage.
+            case SkMask::k3D_Format:
+            case SkMask::kA8_Format:
+                image.colorType = kAlpha_8_SkColorType;
+                break;
+            case SkMask::kARGB32_Format:
+                image.colorType = kN32_SkColorType;
+                break;
+            case SkMask::kLCD16_Format:
+                image.colorType = kRGB_565_SkColorType;
+                break;
+            default:
+                visitor(ctx, i, nullptr);
+                continue;
+        }
+        visitor(ctx, i, &image);
+    }
+}
+
 //
 // core/SkFontArguments.h
 //
@@ -1287,6 +1607,53 @@ extern "C" SkDrawable* C_SkPictureRecorder_finishRecordingAsDrawable(SkPictureRe
     return self->finishRecordingAsDrawable().release();
 }
 
+//
+// core/SkBBHFactory.h
+//
+
+// An R-tree factory that keeps a reference to the hierarchy it created last, so that the
+// hierarchy can be queried after the recording is finished.
+class RustRTreeFactory: public SkBBHFactory {
+public:
+    SkBBoxHierarchy* operator()() const override {
+        // the recorder adopts the returned reference.
+        SkBBoxHierarchy* bbh = SkRTreeFactory()();
+        _bbh = sk_ref_sp(bbh);
+        return bbh;
+    }
+
+    SkBBoxHierarchy* hierarchy() const {
+        return SkSafeRef(_bbh.get());
+    }
+
+private:
+    mutable sk_sp<SkBBoxHierarchy> _bbh;
+};
+
+extern "C" SkBBHFactory* C_RustRTreeFactory_new() {
+    return new RustRTreeFactory();
+}
+
+extern "C" SkBBoxHierarchy* C_RustRTreeFactory_hierarchy(const SkBBHFactory* self) {
+    return static_cast<const RustRTreeFactory*>(self)->hierarchy();
+}
+
+extern "C" void C_SkBBHFactory_delete(SkBBHFactory* self) {
+    delete self;
+}
+
+extern "C" void C_SkBBoxHierarchy_search(
+    const SkBBoxHierarchy* self, const SkRect* query,
+    void* ctx, void (*results)(void* ctx, const int* indices, size_t count)) {
+    std::vector<int> indices;
+    self->search(*query, &indices);
+    results(ctx, indices.data(), indices.size());
+}
+
+extern "C" size_t C_SkBBoxHierarchy_bytesUsed(const SkBBoxHierarchy* self) {
+    return self->bytesUsed();
+}
+
 //
 // core/SkPixelRef.h
 //
@@ -1565,6 +1932,62 @@ extern "C" SkImageGenerator *C_SkImageGenerator_MakeFromPicture(
             sp(cs)).release();
 }
 
+extern "C" struct RustImageGeneratorParam {
+    void* ctx;
+    SkData* (*refEncodedData)(void* ctx);
+    bool (*getPixels)(void* ctx, const SkImageInfo* info, void* pixels, size_t rowBytes);
+    void (*drop)(void* ctx);
+};
+
+class RustImageGenerator: public SkImageGenerator {
+public:
+    RustImageGenerator(const SkImageInfo& info, const RustImageGeneratorParam& param)
+    :SkImageGenerator(info), _param(param) {
+    }
+
+    ~RustImageGenerator() override {
+        _param.drop(_param.ctx);
+    }
+
+protected:
+    sk_sp<SkData> onRefEncodedData() override {
+        return sp(_param.refEncodedData(_param.ctx));
+    }
+
+    bool onGetPixels(const SkImageInfo& info, void* pixels, size_t rowBytes, const Options&) override {
+        return _param.getPixels(_param.ctx, &info, pixels, rowBytes);
+    }
+
+private:
+    RustImageGeneratorParam _param;
+};
+
+extern "C" SkImageGenerator* C_RustImageGenerator_new(const SkImageInfo* info, const RustImageGeneratorParam* param) {
+    return new RustImageGenerator(*info, *param);
+}
+
+//
+// core/SkGraphics.h
+//
+
+typedef SkImageGenerator* (*RustImageGeneratorFactory)(SkData* data);
+
+static RustImageGeneratorFactory rustImageGeneratorFactory = nullptr;
+
+static std::unique_ptr<SkImageGenerator> rustImageGeneratorFromEncodedData(sk_sp<SkData> data) {
+    return std::unique_ptr<SkImageGenerator>(rustImageGeneratorFactory(data.release()));
+}
+
+extern "C" void C_SkGraphics_SetImageGeneratorFromEncodedDataFactory(RustImageGeneratorFactory factory) {
+    rustImageGeneratorFactory = factory;
+    SkGraphics::SetImageGeneratorFromEncodedDataFactory(factory ? rustImageGeneratorFromEncodedData : nullptr);
+}
+
+extern "C" void C_SkGraphics_DumpMemoryStatistics(const RustTraceMemoryDumpParam* param) {
+    RustTraceMemoryDump dump(*param);
+    SkGraphics::DumpMemoryStatistics(&dump);
+}
+
 //
 // core/SkString.h
 //
@@ -1648,6 +2071,63 @@ extern "C" SkPathEffect* C_SkPathEffect_Deserialize(const void* data, size_t len
     return SkPathEffect::Deserialize(data, length).release();
 }
 
+extern "C" struct RustPathEffectParam {
+    void* ctx;
+    bool (*filterPath)(void* ctx, SkPath* dst, const SkPath* src, SkStrokeRec* rec, const SkRect* cullR);
+    void (*computeFastBounds)(void* ctx, SkRect* dst, const SkRect* src);
+    void (*drop)(void* ctx);
+};
+
+class RustPathEffect: public SkPathEffect {
+public:
+    explicit RustPathEffect(const RustPathEffectParam& param)
+    : _param(param) {
+    }
+
+    ~RustPathEffect() override {
+        _param.drop(_param.ctx);
+    }
+
+    // Rust path effects are serialized without their state, and are deserialized as no path
+    // effect. Skia requires a registered factory to write them into a picture.
+    static sk_sp<SkFlattenable> CreateProc(SkReadBuffer&) {
+        return nullptr;
+    }
+
+    Factory getFactory() const override {
+        return CreateProc;
+    }
+
+    const char* getTypeName() const override {
+        return "RustPathEffect";
+    }
+
+protected:
+    bool onFilterPath(SkPath* dst, const SkPath& src, SkStrokeRec* rec, const SkRect* cullR) const override {
+        return _param.filterPath(_param.ctx, dst, &src, rec, cullR);
+    }
+
+    SkRect onComputeFastBounds(const SkRect& src) const override {
+        SkRect dst = src;
+        _param.computeFastBounds(_param.ctx, &dst, &src);
+        return dst;
+    }
+
+private:
+    RustPathEffectParam _param;
+};
+
+extern "C" SkPathEffect* C_RustPathEffect_new(const RustPathEffectParam* param) {
+    return new RustPathEffect(*param);
+}
+
+// Skia sorts its flattenable registry when it is first used, so the factory is registered when
+// the library is loaded.
+static const bool rustPathEffectRegistered = [] {
+    SkFlattenable::Register("RustPathEffect", RustPathEffect::CreateProc);
+    return true;
+}();
+
 //
 // SkPixmap
 //
@@ -2553,6 +3033,45 @@ extern "C" SkDocument* C_SkPDF_MakeDocument(SkWStream* stream, const SkPDF::Meta
     return SkPDF::MakeDocument(stream, *metadata).release();
 }
 
+//
+// utils/SkMultiPictureDocument.h
+//
+
+// procs may be null, if not, they must outlive the document.
+extern "C" SkDocument* C_SkMultiPictureDocument_Make(SkWStream* stream, const RustSerialProcs* procs) {
+    if (procs) {
+        auto skProcs = toSkSerialProcs(procs);
+        return SkMakeMultiPictureDocument(stream, &skProcs).release();
+    }
+    return SkMakeMultiPictureDocument(stream).release();
+}
+
+extern "C" int C_SkMultiPictureDocument_ReadPageCount(const void* data, size_t length) {
+    SkMemoryStream stream(data, length, false);
+    return SkMultiPictureDocumentReadPageCount(&stream);
+}
+
+// procs may be null.
+extern "C" bool C_SkMultiPictureDocument_Read(
+    const void* data, size_t length,
+    SkPicture** pictures, SkSize* sizes, int count,
+    const RustDeserialProcs* procs) {
+    SkMemoryStream stream(data, length, false);
+    std::vector<SkDocumentPage> pages(count);
+    SkDeserialProcs skProcs;
+    if (procs) {
+        skProcs = toSkDeserialProcs(procs);
+    }
+    if (!SkMultiPictureDocumentRead(&stream, pages.data(), count, procs ? &skProcs : nullptr)) {
+        return false;
+    }
+    for (int i = 0; i < count; ++i) {
+        pictures[i] = pages[i].fPicture.release();
+        sizes[i] = pages[i].fSize;
+    }
+    return true;
+}
+
 //
 // pathops/
 //
@@ -2598,3 +3117,589 @@ extern "C" void C_SkInterpolator_setMirror(SkInterpolator* self, bool mirror) {
 extern "C" SkCanvas* C_SkMakeNullCanvas() {
     return SkMakeNullCanvas().release();
 }
+
+//
+// utils/SkNWayCanvas.h
+//
+
+extern "C" SkCanvas* C_SkNWayCanvas_new(int width, int height) {
+    return new SkNWayCanvas(width, height);
+}
+
+extern "C" void C_SkNWayCanvas_addCanvas(SkCanvas* self, SkCanvas* canvas) {
+    static_cast<SkNWayCanvas*>(self)->addCanvas(canvas);
+}
+
+extern "C" void C_SkNWayCanvas_removeAll(SkCanvas* self) {
+    static_cast<SkNWayCanvas*>(self)->removeAll();
+}
+
+//
+// utils/SkNoDrawCanvas.h
+//
+
+extern "C" {
+    enum class RustDrawCommandKind {
+        Save,
+        SaveLayer,
+        Restore,
+        Concat,
+        SetMatrix,
+        ClipRect,
+        ClipRRect,
+        ClipPath,
+        ClipRegion,
+        DrawPaint,
+        DrawBehind,
+        DrawRect,
+        DrawRRect,
+        DrawDRRect,
+        DrawOval,
+        DrawArc,
+        DrawPath,
+        DrawRegion,
+        DrawTextBlob,
+        DrawPoints,
+        DrawVertices,
+        DrawPatch,
+        DrawAtlas,
+        DrawShadow,
+        DrawImage,
+        DrawImageRect,
+        DrawImageNine,
+        DrawImageLattice,
+        DrawEdgeAAQuad,
+        DrawEdgeAAImageSet,
+        DrawAnnotation,
+        DrawPicture,
+        DrawDrawable,
+    };
+
+    // An entry of drawEdgeAAImageSet() without the sk_sp<> of SkCanvas::ImageSetEntry.
+    struct RustImageSetEntry {
+        const SkImage* image;
+        SkRect srcRect;
+        SkRect dstRect;
+        int matrixIndex;
+        float alpha;
+        unsigned aaFlags;
+        bool hasClip;
+    };
+
+    // A flattened drawing command, only the fields that are relevant for the kind are set.
+    struct RustDrawCommand {
+        RustDrawCommandKind kind;
+        const SkPaint* paint;
+        const SkMatrix* matrix;
+        const SkRect* rect;
+        const SkRect* rect2;
+        const SkIRect* irect;
+        const SkRRect* rrect;
+        const SkRRect* rrect2;
+        const SkPath* path;
+        const SkRegion* region;
+        const SkTextBlob* textBlob;
+        const SkVertices* vertices;
+        const SkImage* image;
+        const SkPicture* picture;
+        SkDrawable* drawable;
+        const SkData* data;
+        const char* key;
+        const SkPoint* points;
+        size_t count;
+        const SkPoint* points2;
+        size_t count2;
+        const SkColor* colors;
+        const SkRect* rects;
+        const SkRSXform* xforms;
+        const SkMatrix* matrices;
+        size_t matrixCount;
+        const SkDrawShadowRec* shadowRec;
+        const SkCanvas::Lattice* lattice;
+        const RustImageSetEntry* imageSet;
+        unsigned aaFlags;
+        SkScalar x;
+        SkScalar y;
+        SkColor4f color;
+        SkClipOp clipOp;
+        bool flag;
+        SkCanvas::PointMode pointMode;
+        SkBlendMode blendMode;
+        SkCanvas::SrcRectConstraint constraint;
+        SkCanvas::SaveLayerFlags saveLayerFlags;
+    };
+
+    typedef void (*RustDrawCommandFn)(void* ctx, const RustDrawCommand* command);
+}
+
+// A canvas that does not draw anything, but forwards all drawing commands to a Rust callback.
+// Drawables and nested pictures that SkCanvas does not unroll are reported as single commands.
+class RustDrawCommandCanvas: public SkNoDrawCanvas {
+public:
+    RustDrawCommandCanvas(int width, int height, void* ctx, RustDrawCommandFn onCommand)
+    : SkNoDrawCanvas(width, height), _ctx(ctx), _onCommand(onCommand) {}
+
+protected:
+    void willSave() override {
+        auto cmd = command(RustDrawCommandKind::Save);
+        emit(cmd);
+    }
+
+    SaveLayerStrategy getSaveLayerStrategy(const SaveLayerRec& rec) override {
+        auto cmd = command(RustDrawCommandKind::SaveLayer);
+        cmd.rect = rec.fBounds;
+        cmd.paint = rec.fPaint;
+        cmd.saveLayerFlags = rec.fSaveLayerFlags;
+        emit(cmd);
+        return kNoLayer_SaveLayerStrategy;
+    }
+
+    void willRestore() override {
+        auto cmd = command(RustDrawCommandKind::Restore);
+        emit(cmd);
+    }
+
+    void didConcat(const SkMatrix& matrix) override {
+        auto cmd = command(RustDrawCommandKind::Concat);
+        cmd.matrix = &matrix;
+        emit(cmd);
+    }
+
+    void didSetMatrix(const SkMatrix& matrix) override {
+        auto cmd = command(RustDrawCommandKind::SetMatrix);
+        cmd.matrix = &matrix;
+        emit(cmd);
+    }
+
+    void onClipRect(const SkRect& rect, SkClipOp op, ClipEdgeStyle edgeStyle) override {
+        auto cmd = command(RustDrawCommandKind::ClipRect);
+        cmd.rect = &rect;
+        cmd.clipOp = op;
+        cmd.flag = edgeStyle == kSoft_ClipEdgeStyle;
+        emit(cmd);
+        SkNoDrawCanvas::onClipRect(rect, op, edgeStyle);
+    }
+
+    void onClipRRect(const SkRRect& rrect, SkClipOp op, ClipEdgeStyle edgeStyle) override {
+        auto cmd = command(RustDrawCommandKind::ClipRRect);
+        cmd.rrect = &rrect;
+        cmd.clipOp = op;
+        cmd.flag = edgeStyle == kSoft_ClipEdgeStyle;
+        emit(cmd);
+        SkNoDrawCanvas::onClipRRect(rrect, op, edgeStyle);
+    }
+
+    void onClipPath(const SkPath& path, SkClipOp op, ClipEdgeStyle edgeStyle) override {
+        auto cmd = command(RustDrawCommandKind::ClipPath);
+        cmd.path = &path;
+        cmd.clipOp = op;
+        cmd.flag = edgeStyle == kSoft_ClipEdgeStyle;
+        emit(cmd);
+        SkNoDrawCanvas::onClipPath(path, op, edgeStyle);
+    }
+
+    void onClipRegion(const SkRegion& region, SkClipOp op) override {
+        auto cmd = command(RustDrawCommandKind::ClipRegion);
+        cmd.region = &region;
+        cmd.clipOp = op;
+        emit(cmd);
+        SkNoDrawCanvas::onClipRegion(region, op);
+    }
+
+    void onDrawPaint(const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawPaint);
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawBehind(const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawBehind);
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawRect(const SkRect& rect, const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawRect);
+        cmd.rect = &rect;
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawRRect(const SkRRect& rrect, const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawRRect);
+        cmd.rrect = &rrect;
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawDRRect(const SkRRect& outer, const SkRRect& inner, const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawDRRect);
+        cmd.rrect = &outer;
+        cmd.rrect2 = &inner;
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawOval(const SkRect& oval, const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawOval);
+        cmd.rect = &oval;
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawArc(const SkRect& oval, SkScalar startAngle, SkScalar sweepAngle, bool useCenter, const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawArc);
+        cmd.rect = &oval;
+        cmd.x = startAngle;
+        cmd.y = sweepAngle;
+        cmd.flag = useCenter;
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawPath(const SkPath& path, const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawPath);
+        cmd.path = &path;
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawRegion(const SkRegion& region, const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawRegion);
+        cmd.region = &region;
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawTextBlob(const SkTextBlob* blob, SkScalar x, SkScalar y, const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawTextBlob);
+        cmd.textBlob = blob;
+        cmd.x = x;
+        cmd.y = y;
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawPoints(PointMode mode, size_t count, const SkPoint pts[], const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawPoints);
+        cmd.pointMode = mode;
+        cmd.points = pts;
+        cmd.count = count;
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawVerticesObject(const SkVertices* vertices, const SkVertices::Bone[], int, SkBlendMode mode, const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawVertices);
+        cmd.vertices = vertices;
+        cmd.blendMode = mode;
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawPatch(const SkPoint cubics[12], const SkColor colors[4], const SkPoint texCoords[4], SkBlendMode mode, const SkPaint& paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawPatch);
+        cmd.points = cubics;
+        cmd.count = 12;
+        cmd.colors = colors;
+        cmd.points2 = texCoords;
+        cmd.count2 = texCoords ? 4 : 0;
+        cmd.blendMode = mode;
+        cmd.paint = &paint;
+        emit(cmd);
+    }
+
+    void onDrawAtlas(const SkImage* atlas, const SkRSXform xform[], const SkRect tex[], const SkColor colors[], int count, SkBlendMode mode, const SkRect* cull, const SkPaint* paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawAtlas);
+        cmd.image = atlas;
+        cmd.xforms = xform;
+        cmd.rects = tex;
+        cmd.colors = colors;
+        cmd.count = count;
+        cmd.blendMode = mode;
+        cmd.rect = cull;
+        cmd.paint = paint;
+        emit(cmd);
+    }
+
+    void onDrawShadowRec(const SkPath& path, const SkDrawShadowRec& rec) override {
+        auto cmd = command(RustDrawCommandKind::DrawShadow);
+        cmd.path = &path;
+        cmd.shadowRec = &rec;
+        emit(cmd);
+    }
+
+    void onDrawImage(const SkImage* image, SkScalar left, SkScalar top, const SkPaint* paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawImage);
+        cmd.image = image;
+        cmd.x = left;
+        cmd.y = top;
+        cmd.paint = paint;
+        emit(cmd);
+    }
+
+    void onDrawImageRect(const SkImage* image, const SkRect* src, const SkRect& dst, const SkPaint* paint, SrcRectConstraint constraint) override {
+        auto cmd = command(RustDrawCommandKind::DrawImageRect);
+        cmd.image = image;
+        cmd.rect = src;
+        cmd.rect2 = &dst;
+        cmd.paint = paint;
+        cmd.constraint = constraint;
+        emit(cmd);
+    }
+
+    void onDrawImageNine(const SkImage* image, const SkIRect& center, const SkRect& dst, const SkPaint* paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawImageNine);
+        cmd.image = image;
+        cmd.irect = &center;
+        cmd.rect2 = &dst;
+        cmd.paint = paint;
+        emit(cmd);
+    }
+
+    void onDrawImageLattice(const SkImage* image, const Lattice& lattice, const SkRect& dst, const SkPaint* paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawImageLattice);
+        cmd.image = image;
+        cmd.lattice = &lattice;
+        cmd.rect2 = &dst;
+        cmd.paint = paint;
+        emit(cmd);
+    }
+
+    void onDrawEdgeAAQuad(const SkRect& rect, const SkPoint clip[4], QuadAAFlags aaFlags, const SkColor4f& color, SkBlendMode mode) override {
+        auto cmd = command(RustDrawCommandKind::DrawEdgeAAQuad);
+        cmd.rect = &rect;
+        cmd.points = clip;
+        cmd.count = clip ? 4 : 0;
+        cmd.aaFlags = aaFlags;
+        cmd.color = color;
+        cmd.blendMode = mode;
+        emit(cmd);
+    }
+
+    void onDrawEdgeAAImageSet(const ImageSetEntry set[], int count, const SkPoint dstClips[], const SkMatrix preViewMatrices[], const SkPaint* paint, SrcRectConstraint constraint) override {
+        std::vector<RustImageSetEntry> entries;
+        size_t clipCount = 0;
+        int matrixCount = 0;
+        for (int i = 0; i < count; ++i) {
+            const auto& entry = set[i];
+            entries.push_back({entry.fImage.get(), entry.fSrcRect, entry.fDstRect, entry.fMatrixIndex, entry.fAlpha, entry.fAAFlags, entry.fHasClip});
+            clipCount += entry.fHasClip ? 4 : 0;
+            matrixCount = std::max(matrixCount, entry.fMatrixIndex + 1);
+        }
+
+        auto cmd = command(RustDrawCommandKind::DrawEdgeAAImageSet);
+        cmd.imageSet = entries.data();
+        cmd.count = count;
+        cmd.points2 = dstClips;
+        cmd.count2 = clipCount;
+        cmd.matrices = preViewMatrices;
+        cmd.matrixCount = matrixCount;
+        cmd.paint = paint;
+        cmd.constraint = constraint;
+        emit(cmd);
+    }
+
+    void onDrawAnnotation(const SkRect& rect, const char key[], SkData* value) override {
+        auto cmd = command(RustDrawCommandKind::DrawAnnotation);
+        cmd.rect = &rect;
+        cmd.key = key;
+        cmd.data = value;
+        emit(cmd);
+    }
+
+    void onDrawPicture(const SkPicture* picture, const SkMatrix* matrix, const SkPaint* paint) override {
+        auto cmd = command(RustDrawCommandKind::DrawPicture);
+        cmd.picture = picture;
+        cmd.matrix = matrix;
+        cmd.paint = paint;
+        emit(cmd);
+    }
+
+    void onDrawDrawable(SkDrawable* drawable, const SkMatrix* matrix) override {
+        auto cmd = command(RustDrawCommandKind::DrawDrawable);
+        cmd.drawable = drawable;
+        cmd.matrix = matrix;
+        emit(cmd);
+    }
+
+private:
+    static RustDrawCommand command(RustDrawCommandKind kind) {
+        RustDrawCommand cmd = {};
+        cmd.kind = kind;
+        return cmd;
+    }
+
+    void emit(const RustDrawCommand& cmd) {
+        _onCommand(_ctx, &cmd);
+    }
+
+    void* _ctx;
+    RustDrawCommandFn _onCommand;
+};
+
+extern "C" SkCanvas* C_RustDrawCommandCanvas_new(int width, int height, void* ctx, RustDrawCommandFn onCommand) {
+    return new RustDrawCommandCanvas(width, height, ctx, onCommand);
+}
+
+//
+// utils/SkPaintFilterCanvas.h
+//
+
+extern "C" {
+    enum class RustPaintFilterDrawType {
+        Paint,
+        Behind,
+        Points,
+        Rect,
+        RRect,
+        DRRect,
+        Oval,
+        Arc,
+        Path,
+        Region,
+        TextBlob,
+        Image,
+        Vertices,
+        Patch,
+        Atlas,
+        Picture,
+        Other,
+    };
+
+    typedef bool (*RustPaintFilterFn)(void* ctx, SkPaint* paint, RustPaintFilterDrawType drawType);
+}
+
+// SkPaintFilterCanvas does not tell onFilter() what is drawn, so the draw calls record their type
+// before they are forwarded to the base class.
+class RustPaintFilterCanvas: public SkPaintFilterCanvas {
+public:
+    RustPaintFilterCanvas(SkCanvas* canvas, void* ctx, RustPaintFilterFn onFilter)
+    : SkPaintFilterCanvas(canvas), _ctx(ctx), _onFilter(onFilter) {}
+
+protected:
+    bool onFilter(SkPaint& paint) const override {
+        return _onFilter(_ctx, &paint, _drawType);
+    }
+
+    void onDrawPaint(const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Paint);
+        SkPaintFilterCanvas::onDrawPaint(paint);
+    }
+
+    void onDrawBehind(const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Behind);
+        SkPaintFilterCanvas::onDrawBehind(paint);
+    }
+
+    void onDrawPoints(PointMode mode, size_t count, const SkPoint pts[], const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Points);
+        SkPaintFilterCanvas::onDrawPoints(mode, count, pts, paint);
+    }
+
+    void onDrawRect(const SkRect& rect, const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Rect);
+        SkPaintFilterCanvas::onDrawRect(rect, paint);
+    }
+
+    void onDrawRRect(const SkRRect& rrect, const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::RRect);
+        SkPaintFilterCanvas::onDrawRRect(rrect, paint);
+    }
+
+    void onDrawDRRect(const SkRRect& outer, const SkRRect& inner, const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::DRRect);
+        SkPaintFilterCanvas::onDrawDRRect(outer, inner, paint);
+    }
+
+    void onDrawOval(const SkRect& oval, const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Oval);
+        SkPaintFilterCanvas::onDrawOval(oval, paint);
+    }
+
+    void onDrawArc(const SkRect& oval, SkScalar startAngle, SkScalar sweepAngle, bool useCenter, const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Arc);
+        SkPaintFilterCanvas::onDrawArc(oval, startAngle, sweepAngle, useCenter, paint);
+    }
+
+    void onDrawPath(const SkPath& path, const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Path);
+        SkPaintFilterCanvas::onDrawPath(path, paint);
+    }
+
+    void onDrawRegion(const SkRegion& region, const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Region);
+        SkPaintFilterCanvas::onDrawRegion(region, paint);
+    }
+
+    void onDrawTextBlob(const SkTextBlob* blob, SkScalar x, SkScalar y, const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::TextBlob);
+        SkPaintFilterCanvas::onDrawTextBlob(blob, x, y, paint);
+    }
+
+    void onDrawImage(const SkImage* image, SkScalar left, SkScalar top, const SkPaint* paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Image);
+        SkPaintFilterCanvas::onDrawImage(image, left, top, paint);
+    }
+
+    void onDrawImageRect(const SkImage* image, const SkRect* src, const SkRect& dst, const SkPaint* paint, SrcRectConstraint constraint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Image);
+        SkPaintFilterCanvas::onDrawImageRect(image, src, dst, paint, constraint);
+    }
+
+    void onDrawImageNine(const SkImage* image, const SkIRect& center, const SkRect& dst, const SkPaint* paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Image);
+        SkPaintFilterCanvas::onDrawImageNine(image, center, dst, paint);
+    }
+
+    void onDrawImageLattice(const SkImage* image, const Lattice& lattice, const SkRect& dst, const SkPaint* paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Image);
+        SkPaintFilterCanvas::onDrawImageLattice(image, lattice, dst, paint);
+    }
+
+    void onDrawVerticesObject(const SkVertices* vertices, const SkVertices::Bone bones[], int boneCount, SkBlendMode mode, const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Vertices);
+        SkPaintFilterCanvas::onDrawVerticesObject(vertices, bones, boneCount, mode, paint);
+    }
+
+    void onDrawPatch(const SkPoint cubics[12], const SkColor colors[4], const SkPoint texCoords[4], SkBlendMode mode, const SkPaint& paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Patch);
+        SkPaintFilterCanvas::onDrawPatch(cubics, colors, texCoords, mode, paint);
+    }
+
+    void onDrawAtlas(const SkImage* atlas, const SkRSXform xform[], const SkRect tex[], const SkColor colors[], int count, SkBlendMode mode, const SkRect* cull, const SkPaint* paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Atlas);
+        SkPaintFilterCanvas::onDrawAtlas(atlas, xform, tex, colors, count, mode, cull, paint);
+    }
+
+    void onDrawPicture(const SkPicture* picture, const SkMatrix* matrix, const SkPaint* paint) override {
+        DrawTypeScope scope(this, RustPaintFilterDrawType::Picture);
+        SkPaintFilterCanvas::onDrawPicture(picture, matrix, paint);
+    }
+
+private:
+    // Restores the previous draw type, because pictures are played back through this canvas.
+    class DrawTypeScope {
+    public:
+        DrawTypeScope(RustPaintFilterCanvas* canvas, RustPaintFilterDrawType drawType)
+        : _canvas(canvas), _previous(canvas->_drawType) {
+            canvas->_drawType = drawType;
+        }
+
+        ~DrawTypeScope() {
+            _canvas->_drawType = _previous;
+        }
+
+    private:
+        RustPaintFilterCanvas* _canvas;
+        RustPaintFilterDrawType _previous;
+    };
+
+    void* _ctx;
+    RustPaintFilterFn _onFilter;
+    RustPaintFilterDrawType _drawType = RustPaintFilterDrawType::Other;
+};
+
+extern "C" SkCanvas* C_RustPaintFilterCanvas_new(SkCanvas* canvas, void* ctx, RustPaintFilterFn onFilter) {
+    return new RustPaintFilterCanvas(canvas, ctx, onFilter);
+}
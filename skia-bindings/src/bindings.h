@@ -3,6 +3,7 @@
 
 #include "include/core/SkRefCnt.h"
 #include "include/core/SkString.h"
+#include "include/core/SkTraceMemoryDump.h"
 #include <vector>
 
 template<typename T>
@@ -21,4 +22,39 @@ struct SkStrings {
     std::vector<SkString> strings;
 };
 
+// Used in SkGraphics::DumpMemoryStatistics() and GrContext::dumpMemoryStatistics()
+
+extern "C" struct RustTraceMemoryDumpParam {
+    void* ctx;
+    void (*dumpNumericValue)(void* ctx, const char* dumpName, const char* valueName, const char* units, uint64_t value);
+    void (*setMemoryBacking)(void* ctx, const char* dumpName, const char* backingType, const char* backingObjectId);
+    SkTraceMemoryDump::LevelOfDetail (*getRequestedDetails)(const void* ctx);
+};
+
+class RustTraceMemoryDump: public SkTraceMemoryDump {
+public:
+    explicit RustTraceMemoryDump(const RustTraceMemoryDumpParam& param)
+    :_param(param) {
+    }
+
+    void dumpNumericValue(const char* dumpName, const char* valueName, const char* units, uint64_t value) override {
+        _param.dumpNumericValue(_param.ctx, dumpName, valueName, units, value);
+    }
+
+    void setMemoryBacking(const char* dumpName, const char* backingType, const char* backingObjectId) override {
+        _param.setMemoryBacking(_param.ctx, dumpName, backingType, backingObjectId);
+    }
+
+    // Discardable memory is not used by the Skia configurations the bindings are built with.
+    void setDiscardableMemoryBacking(const char*, const SkDiscardableMemory&) override {
+    }
+
+    LevelOfDetail getRequestedDetails() const override {
+        return _param.getRequestedDetails(_param.ctx);
+    }
+
+private:
+    RustTraceMemoryDumpParam _param;
+};
+
 #endif //SKIA_BINDINGS_BINDINGS_H
@@ -1,6 +1,7 @@
 #include "bindings.h"
 #include "include/gpu/GrContext.h"
 #include "include/gpu/GrBackendDrawableInfo.h"
+#include "include/gpu/mock/GrMockTypes.h"
 #include "include/core/SkDrawable.h"
 #include "include/core/SkSurface.h"
 #include "include/core/SkSurfaceCharacterization.h"
@@ -202,6 +203,23 @@ extern "C" void C_GrContext_compressedBackendFormat(const GrContext* self, SkIma
     *result = self->compressedBackendFormat(compression);
 }
 
+extern "C" void C_GrContext_dumpMemoryStatistics(const GrContext* self, const RustTraceMemoryDumpParam* param) {
+    RustTraceMemoryDump dump(*param);
+    self->dumpMemoryStatistics(&dump);
+}
+
+extern "C" GrContext* C_GrContext_MakeMock(const GrMockOptions* options) {
+    return GrContext::MakeMock(options).release();
+}
+
+//
+// gpu/mock/GrMockTypes.h
+//
+
+extern "C" void C_GrMockOptions_Construct(GrMockOptions* uninitialized) {
+    new(uninitialized) GrMockOptions();
+}
+
 //
 // gpu/GrBackendDrawableInfo.h
 //
@@ -170,6 +170,94 @@ extern "C" void C_RustRunHandler_construct(RustRunHandler* uninitialized, const
     new(uninitialized)RustRunHandler(*param);
 }
 
+extern "C" struct RustRunIteratorParam {
+    void* ctx;
+    void (*consume)(void* ctx);
+    size_t (*endOfCurrentRun)(const void* ctx);
+    bool (*atEnd)(const void* ctx);
+    void (*drop)(void* ctx);
+};
+
+template<typename Base, typename Current>
+class RustRunIterator: public Base {
+public:
+    RustRunIterator(const RustRunIteratorParam& param, Current current)
+    :_param(param), _current(current) {
+    }
+
+    ~RustRunIterator() override {
+        _param.drop(_param.ctx);
+    }
+
+    void consume() override {
+        _param.consume(_param.ctx);
+    }
+
+    size_t endOfCurrentRun() const override {
+        return _param.endOfCurrentRun(_param.ctx);
+    }
+
+    bool atEnd() const override {
+        return _param.atEnd(_param.ctx);
+    }
+
+protected:
+    RustRunIteratorParam _param;
+    Current _current;
+};
+
+class RustFontRunIterator: public RustRunIterator<SkShaper::FontRunIterator, const SkFont* (*)(const void*)> {
+public:
+    using RustRunIterator::RustRunIterator;
+
+    const SkFont& currentFont() const override {
+        return *_current(_param.ctx);
+    }
+};
+
+class RustBiDiRunIterator: public RustRunIterator<SkShaper::BiDiRunIterator, uint8_t (*)(const void*)> {
+public:
+    using RustRunIterator::RustRunIterator;
+
+    uint8_t currentLevel() const override {
+        return _current(_param.ctx);
+    }
+};
+
+class RustScriptRunIterator: public RustRunIterator<SkShaper::ScriptRunIterator, SkFourByteTag (*)(const void*)> {
+public:
+    using RustRunIterator::RustRunIterator;
+
+    SkFourByteTag currentScript() const override {
+        return _current(_param.ctx);
+    }
+};
+
+class RustLanguageRunIterator: public RustRunIterator<SkShaper::LanguageRunIterator, const char* (*)(const void*)> {
+public:
+    using RustRunIterator::RustRunIterator;
+
+    const char* currentLanguage() const override {
+        return _current(_param.ctx);
+    }
+};
+
+extern "C" SkShaper::FontRunIterator* C_RustFontRunIterator_new(const RustRunIteratorParam* param, const SkFont* (*currentFont)(const void*)) {
+    return new RustFontRunIterator(*param, currentFont);
+}
+
+extern "C" SkShaper::BiDiRunIterator* C_RustBiDiRunIterator_new(const RustRunIteratorParam* param, uint8_t (*currentLevel)(const void*)) {
+    return new RustBiDiRunIterator(*param, currentLevel);
+}
+
+extern "C" SkShaper::ScriptRunIterator* C_RustScriptRunIterator_new(const RustRunIteratorParam* param, SkFourByteTag (*currentScript)(const void*)) {
+    return new RustScriptRunIterator(*param, currentScript);
+}
+
+extern "C" SkShaper::LanguageRunIterator* C_RustLanguageRunIterator_new(const RustRunIteratorParam* param, const char* (*currentLanguage)(const void*)) {
+    return new RustLanguageRunIterator(*param, currentLanguage);
+}
+
 extern "C" void
 C_SkShaper_shape(const SkShaper *self, const char *utf8, size_t utf8Bytes, const SkFont *srcFont, bool leftToRight,
                  SkScalar width, SkShaper::RunHandler *runHandler) {
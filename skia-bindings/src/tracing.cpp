@@ -0,0 +1,82 @@
+#include <cstddef>
+#include <map>
+#include <string>
+#include <type_traits>
+
+#include "bindings.h"
+#include "include/utils/SkEventTracer.h"
+#include "include/private/SkMutex.h"
+#include "src/core/SkTraceEventCommon.h"
+
+//
+// utils/SkEventTracer.h
+//
+
+extern "C" struct RustEventTracerParam {
+    bool (*isCategoryEnabled)(const char* category);
+    uint64_t (*begin)(const char* category, const char* name);
+    void (*end)(uint64_t handle);
+    void (*instant)(const char* category, const char* name);
+};
+
+class RustEventTracer: public SkEventTracer {
+public:
+    explicit RustEventTracer(const RustEventTracerParam& param)
+    :_param(param) {
+    }
+
+    const uint8_t* getCategoryGroupEnabled(const char* name) override {
+        SkAutoMutexExclusive lock(_mutex);
+        auto found = _categories.find(name);
+        if (found == _categories.end()) {
+            found = _categories.emplace(name, Category()).first;
+            // map nodes are stable, so the key can be referred to for the lifetime of the tracer.
+            found->second.enabled = _param.isCategoryEnabled(name) ? kEnabledForRecording_CategoryGroupEnabledFlags : 0;
+            found->second.name = found->first.c_str();
+        }
+        return &found->second.enabled;
+    }
+
+    const char* getCategoryGroupName(const uint8_t* categoryEnabledFlag) override {
+        auto category = reinterpret_cast<const Category*>(
+            reinterpret_cast<const char*>(categoryEnabledFlag) - offsetof(Category, enabled));
+        return category->name;
+    }
+
+    Handle addTraceEvent(char phase, const uint8_t* categoryEnabledFlag, const char* name, uint64_t,
+                         int, const char**, const uint8_t*, const uint64_t*, uint8_t) override {
+        auto category = getCategoryGroupName(categoryEnabledFlag);
+        switch (phase) {
+            // ends in updateTraceEventDuration()
+            case TRACE_EVENT_PHASE_COMPLETE:
+                return _param.begin(category, name);
+            case TRACE_EVENT_PHASE_INSTANT:
+                _param.instant(category, name);
+                return 0;
+            default:
+                return 0;
+        }
+    }
+
+    void updateTraceEventDuration(const uint8_t*, const char*, Handle handle) override {
+        if (handle) {
+            _param.end(handle);
+        }
+    }
+
+private:
+    // standard-layout, so that getCategoryGroupName() can get from the flag to its Category.
+    struct Category {
+        uint8_t enabled;
+        const char* name;
+    };
+    static_assert(std::is_standard_layout<Category>::value, "Category must be standard-layout");
+
+    RustEventTracerParam _param;
+    SkMutex _mutex;
+    std::map<std::string, Category> _categories;
+};
+
+extern "C" bool C_SkEventTracer_SetRustInstance(const RustEventTracerParam* param) {
+    return SkEventTracer::SetInstance(new RustEventTracer(*param));
+}
@@ -78,3 +78,28 @@ impl RCHandle<SkCodec> {
         ))
     }
 }
+
+#[cfg(feature = "dng")]
+#[test]
+fn test_decode_dng() {
+    use crate::{ColorType, Image};
+
+    // an uncompressed 16x8 linear DNG, with 8 bit RGB samples that form a horizontal gradient
+    // from black to white, see tests/resources/README.md.
+    let dng = Data::new_copy(include_bytes!("../../tests/resources/linear_gradient.dng"));
+
+    let mut codec = Codec::from_data(dng.clone()).expect("DNG codec");
+    assert!(codec.encoded_format() == EncodedImageFormat::DNG);
+    assert_eq!(codec.dimensions(), ISize::new(16, 8));
+
+    let info = codec.info().with_color_type(ColorType::RGBA8888);
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+    let result = unsafe { codec.get_pixels(&info, pixels.as_mut_ptr() as _, row_bytes) };
+    assert_eq!(result, CodecResult::Success);
+    // the red channel of the left column is darker than the one of the right column.
+    assert!(pixels[0] < pixels[row_bytes - 4]);
+
+    let image = Image::from_encoded(dng, None).expect("DNG image");
+    assert_eq!(image.dimensions(), ISize::new(16, 8));
+}
@@ -92,7 +92,7 @@ pub use flattenable::*;
 pub mod font;
 #[deprecated(since = "0.12.0", note = "use font::Edging")]
 pub use font::Edging as FontEdging;
-pub use font::Font;
+pub use font::{Font, GlyphMask};
 
 pub mod font_arguments;
 #[deprecated(
@@ -197,9 +197,9 @@ pub mod path_effect;
 pub use path_effect::point_data::PointFlags as PointDataPointFlags;
 #[deprecated(since = "0.12.0", note = "use path_effect::DashInfo")]
 pub use path_effect::DashInfo as PathEffectDashInfo;
-pub use path_effect::PathEffect;
 #[deprecated(since = "0.12.0", note = "use path_effect::PointData")]
 pub use path_effect::PointData as PathEffectPointData;
+pub use path_effect::{PathEffect, PathEffectImpl};
 
 pub mod path_measure;
 pub use path_measure::PathMeasure;
@@ -252,6 +252,9 @@ pub use rsxform::*;
 mod scalar_;
 pub use scalar_::*;
 
+mod serial_procs;
+pub use serial_procs::*;
+
 pub mod shader;
 #[deprecated(since = "0.12.0", note = "use shader::GradientInfo")]
 pub use shader::GradientInfo as ShaderGradientInfo;
@@ -300,8 +303,8 @@ pub use self::tile_mode::*;
 mod time;
 pub use time::*;
 
-mod trace_memory_dump;
-pub use trace_memory_dump::*;
+pub mod trace_memory_dump;
+pub use trace_memory_dump::TraceMemoryDump;
 
 pub mod typeface;
 #[deprecated(since = "0.12.0", note = "use typeface::LocalizedString")]
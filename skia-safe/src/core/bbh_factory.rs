@@ -1,17 +1,132 @@
 use crate::prelude::*;
-use skia_bindings::{SkBBHFactory, SkBBoxHierarchy};
+use crate::{Point, Rect};
+use skia_bindings as sb;
+use skia_bindings::{SkBBHFactory, SkBBoxHierarchy, SkRefCntBase};
+use std::ffi::c_void;
+use std::slice;
 
-// TODO: complete the wrapper
+/// A spatial index of the bounds of recorded drawing commands, see
+/// `BBHFactory::bbox_hierarchy()`.
 pub type BBoxHierarchy = RCHandle<SkBBoxHierarchy>;
 
-// TODO: complete the wrapper
-pub type BBHFactory = Handle<SkBBHFactory>;
+impl NativeRefCountedBase for SkBBoxHierarchy {
+    type Base = SkRefCntBase;
+}
+
+impl RCHandle<SkBBoxHierarchy> {
+    /// Returns the indices of the recorded commands whose bounds intersect `query`, in
+    /// recording order.
+    pub fn search(&self, query: impl AsRef<Rect>) -> Vec<usize> {
+        let mut indices: Vec<usize> = Vec::new();
+        unsafe {
+            sb::C_SkBBoxHierarchy_search(
+                self.native(),
+                query.as_ref().native(),
+                &mut indices as *mut Vec<usize> as *mut c_void,
+                Some(collect_indices),
+            )
+        }
+        indices.sort_unstable();
+        indices
+    }
+
+    /// Returns the indices of the recorded commands whose bounds contain `point`, in recording
+    /// order.
+    pub fn search_point(&self, point: impl Into<Point>) -> Vec<usize> {
+        let point = point.into();
+        self.search(Rect::new(
+            point.x,
+            point.y,
+            next_up(point.x),
+            next_up(point.y),
+        ))
+    }
+
+    pub fn bytes_used(&self) -> usize {
+        unsafe { sb::C_SkBBoxHierarchy_bytesUsed(self.native()) }
+    }
+}
+
+unsafe extern "C" fn collect_indices(ctx: *mut c_void, indices: *const i32, count: usize) {
+    let result = &mut *(ctx as *mut Vec<usize>);
+    if count > 0 {
+        result.extend(
+            slice::from_raw_parts(indices, count)
+                .iter()
+                .map(|i| *i as usize),
+        );
+    }
+}
+
+/// Returns the smallest float that is larger than `v`, so that a point can be queried as a
+/// rectangle with a non-empty area.
+fn next_up(v: f32) -> f32 {
+    if v.is_nan() || v == f32::INFINITY {
+        v
+    } else if v == 0.0 {
+        f32::from_bits(1)
+    } else if v > 0.0 {
+        f32::from_bits(v.to_bits() + 1)
+    } else {
+        f32::from_bits(v.to_bits() - 1)
+    }
+}
+
+/// Creates the bounding box hierarchy of a recording, see `PictureRecorder::begin_recording()`.
+pub type BBHFactory = RefHandle<SkBBHFactory>;
 
 impl NativeDrop for SkBBHFactory {
     fn drop(&mut self) {
-        unimplemented!()
+        unsafe { sb::C_SkBBHFactory_delete(self) }
+    }
+}
+
+impl RefHandle<SkBBHFactory> {
+    /// Creates a factory for R-trees.
+    pub fn new_rtree() -> BBHFactory {
+        BBHFactory::from_ptr(unsafe { sb::C_RustRTreeFactory_new() }).unwrap()
+    }
+
+    /// Returns the hierarchy the factory created for the most recent recording.
+    ///
+    /// The bounds of the recorded commands are inserted when the recording is finished.
+    pub fn bbox_hierarchy(&self) -> Option<BBoxHierarchy> {
+        BBoxHierarchy::from_ptr(unsafe { sb::C_RustRTreeFactory_hierarchy(self.native()) })
     }
 }
 
-// TODO: complete the implementation
-impl Handle<SkBBHFactory> {}
+#[cfg(test)]
+mod tests {
+    use super::BBHFactory;
+    use crate::{Color, IRect, Paint, PictureRecorder, Rect, Region, Surface};
+
+    #[test]
+    fn recorded_commands_can_be_queried_and_replayed_by_region() {
+        let mut factory = BBHFactory::new_rtree();
+        assert!(factory.bbox_hierarchy().is_none());
+        let mut recorder = PictureRecorder::new();
+        let canvas =
+            recorder.begin_recording(Rect::new(0.0, 0.0, 32.0, 32.0), Some(&mut factory), None);
+        let mut paint = Paint::default();
+        paint.set_color(Color::RED);
+        canvas.draw_rect(Rect::new(0.0, 0.0, 16.0, 16.0), &paint);
+        paint.set_color(Color::BLUE);
+        canvas.draw_rect(Rect::new(16.0, 16.0, 32.0, 32.0), &paint);
+        let picture = recorder.finish_recording_as_picture(None).unwrap();
+        let bbh = factory.bbox_hierarchy().unwrap();
+
+        assert_eq!(bbh.search(Rect::new(0.0, 0.0, 32.0, 32.0)), vec![0, 1]);
+        assert_eq!(bbh.search(Rect::new(2.0, 2.0, 4.0, 4.0)), vec![0]);
+        assert_eq!(bbh.search_point((20, 20)), vec![1]);
+        assert!(bbh.search_point((20, 4)).is_empty());
+        assert!(bbh.bytes_used() > 0);
+
+        let mut surface = Surface::new_raster_n32_premul((32, 32)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        let region = Region::from_rects(&[IRect::new(0, 0, 16, 16)]);
+        picture.playback_region(surface.canvas(), &region);
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((8, 8)), Color::RED);
+        assert_eq!(pixmap.get_color((24, 24)), Color::WHITE);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use crate::{
-    AlphaType, Color, ColorSpace, ColorType, IPoint, IRect, ISize, ImageInfo, Paint, PixelRef,
-    Pixmap,
+    AlphaType, Color, ColorSpace, ColorType, IPoint, IRect, ISize, ImageInfo, Paint, Pixel,
+    PixelRef, Pixmap,
 };
 use crate::{Matrix, Shader, TileMode};
 use skia_bindings as sb;
@@ -37,6 +37,40 @@ impl Handle<SkBitmap> {
         Pixmap::from_native_ref(&self.native().fPixmap)
     }
 
+    /// Returns the pixels as a slice of `P`, see `Pixmap::pixels()`.
+    pub fn pixels<P: Pixel>(&self) -> Option<&[P]> {
+        self.pixmap().pixels()
+    }
+
+    /// Returns the pixels as a mutable slice of `P`. Call `notify_pixels_changed()` after the
+    /// pixels were modified.
+    ///
+    /// # Safety
+    /// The pixels may be shared with clones of the bitmap and with images that were created
+    /// from it. The caller must make sure that the bitmap is not immutable and that nothing else
+    /// reads or writes the pixels while the slice is alive.
+    pub unsafe fn pixels_mut<P: Pixel>(&mut self) -> Option<&mut [P]> {
+        self.pixmap_mut().pixels_mut()
+    }
+
+    /// Returns an iterator over the rows of pixels, see `Pixmap::rows()`.
+    pub fn rows<P: Pixel>(&self) -> Option<impl Iterator<Item = &[P]>> {
+        self.pixmap().rows()
+    }
+
+    /// Returns an iterator over the mutable rows of pixels. Call `notify_pixels_changed()` after
+    /// the pixels were modified.
+    ///
+    /// # Safety
+    /// The same requirements as for `pixels_mut()` apply.
+    pub unsafe fn rows_mut<P: Pixel>(&mut self) -> Option<impl Iterator<Item = &mut [P]>> {
+        self.pixmap_mut().rows_mut()
+    }
+
+    fn pixmap_mut(&mut self) -> &mut Pixmap {
+        Pixmap::from_native_ref_mut(&mut self.native_mut().fPixmap)
+    }
+
     pub fn info(&self) -> &ImageInfo {
         self.pixmap().info()
     }
@@ -419,3 +453,24 @@ fn shader_with_tilemode() {
     let bm = Bitmap::new();
     let _shader = bm.to_shader((TileMode::Decal, TileMode::Mirror), None);
 }
+
+#[test]
+fn typed_pixel_access() {
+    let mut bm = Bitmap::new();
+    bm.alloc_pixels_info(&ImageInfo::new_a8((3, 2)), 4);
+    assert!(bm.pixels::<u32>().is_none());
+    assert_eq!(bm.pixels::<u8>().unwrap().len(), 4 + 3);
+
+    for (y, row) in unsafe { bm.rows_mut::<u8>() }.unwrap().enumerate() {
+        assert_eq!(row.len(), 3);
+        for (x, pixel) in row.iter_mut().enumerate() {
+            *pixel = (y * 3 + x) as u8 * 10;
+        }
+    }
+    bm.notify_pixels_changed();
+
+    assert_eq!(bm.pixels::<u8>().unwrap()[4..], [30, 40, 50]);
+    let rows: Vec<&[u8]> = bm.rows().unwrap().collect();
+    assert_eq!(rows, [&[0u8, 10, 20][..], &[30, 40, 50][..]]);
+    assert_eq!(bm.get_color((2, 1)).a(), 50);
+}
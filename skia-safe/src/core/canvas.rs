@@ -120,6 +120,16 @@ fn test_src_rect_constraint_naming() {
     let _ = SrcRectConstraint::Fast;
 }
 
+bitflags! {
+    /// The edges of a quad that are anti-aliased.
+    pub struct QuadAAFlags: u32 {
+        const LEFT = sb::SkCanvas_QuadAAFlags_kLeft_QuadAAFlag as _;
+        const TOP = sb::SkCanvas_QuadAAFlags_kTop_QuadAAFlag as _;
+        const RIGHT = sb::SkCanvas_QuadAAFlags_kRight_QuadAAFlag as _;
+        const BOTTOM = sb::SkCanvas_QuadAAFlags_kBottom_QuadAAFlag as _;
+    }
+}
+
 /// Provides access to Canvas's pixels.
 /// Returned by Canvas::access_top_layer_pixels()
 pub struct TopLayerPixels<'a> {
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use skia_bindings as sb;
 use skia_bindings::SkData;
-use std::ffi::{CStr, CString};
+use std::ffi::{c_void, CStr, CString};
 use std::ops::Deref;
 use std::slice;
 
@@ -58,8 +58,20 @@ impl RCHandle<SkData> {
         }
     }
 
-    // TODO:
-    // pub fn writable_data(&mut self) -> &mut [u8]
+    /// Returns the bytes for writing.
+    ///
+    /// # Safety
+    /// The data must not be shared and its memory must be writable, which is the case for
+    /// `Data` created with `new_uninitialized()`, `new_copy()`, `from_vec()`, or
+    /// `from_boxed_slice()`.
+    pub unsafe fn writable_data(&mut self) -> &mut [u8] {
+        debug_assert!(self.native().unique());
+        let bytes = self.native().fPtr as *mut u8;
+        if bytes.is_null() {
+            return &mut [];
+        }
+        slice::from_raw_parts_mut(bytes, self.size())
+    }
 
     pub fn copy_range(&self, offset: usize, buffer: &mut [u8]) -> &Self {
         buffer.copy_from_slice(&self.as_bytes()[offset..offset + buffer.len()]);
@@ -72,6 +84,47 @@ impl RCHandle<SkData> {
             .unwrap()
     }
 
+    /// Constructs Data that takes ownership of the bytes of a `Vec` without copying them.
+    pub fn from_vec(data: Vec<u8>) -> Data {
+        Self::from_boxed_slice(data.into_boxed_slice())
+    }
+
+    /// Constructs Data that takes ownership of the bytes without copying them.
+    pub fn from_boxed_slice(data: Box<[u8]>) -> Data {
+        let length = data.len();
+        let ptr = Box::into_raw(data) as *mut u8;
+        Data::from_ptr(unsafe {
+            sb::C_SkData_MakeWithProc(ptr as _, length, Some(release_boxed_slice), length as _)
+        })
+        .unwrap()
+    }
+
+    /// Constructs Data that refers to static bytes without copying them.
+    pub fn from_static(data: &'static [u8]) -> Data {
+        Data::from_ptr(unsafe { sb::C_SkData_MakeWithoutCopy(data.as_ptr() as _, data.len()) })
+            .unwrap()
+    }
+
+    /// Constructs Data that refers to the bytes without copying them and calls `release` when
+    /// the Data is freed, which may happen on another thread.
+    ///
+    /// # Safety
+    /// The bytes must stay valid and unmodified until `release` is called.
+    pub unsafe fn new_bytes_with_release(
+        data: &[u8],
+        release: impl FnOnce() + Send + 'static,
+    ) -> Data {
+        let release: Box<Box<dyn FnOnce() + Send>> = Box::new(Box::new(release));
+        Data::from_ptr(sb::C_SkData_MakeWithProc(
+            data.as_ptr() as _,
+            data.len(),
+            Some(call_release),
+            Box::into_raw(release) as _,
+        ))
+        .unwrap()
+    }
+
+    /// Constructs Data with uninitialized bytes, use `writable_data()` to initialize them.
     pub unsafe fn new_uninitialized(length: usize) -> Data {
         Data::from_ptr(sb::C_SkData_MakeUninitialized(length)).unwrap()
     }
@@ -104,6 +157,16 @@ impl RCHandle<SkData> {
     }
 }
 
+unsafe extern "C" fn release_boxed_slice(ptr: *const c_void, length: *mut c_void) {
+    let slice = slice::from_raw_parts_mut(ptr as *mut u8, length as usize);
+    drop(Box::from_raw(slice as *mut [u8]))
+}
+
+unsafe extern "C" fn call_release(_ptr: *const c_void, ctx: *mut c_void) {
+    let release = Box::from_raw(ctx as *mut Box<dyn FnOnce() + Send>);
+    (*release)()
+}
+
 #[cfg(test)]
 impl RefCount for SkData {
     fn ref_cnt(&self) -> usize {
@@ -118,3 +181,41 @@ fn data_supports_equals() {
     let d2 = Data::new_copy(x);
     assert!(d1 == d2)
 }
+
+#[test]
+fn data_from_vec_does_not_copy() {
+    let v = vec![1u8, 2, 3, 4];
+    let ptr = v.as_ptr();
+    let data = Data::from_vec(v);
+    assert_eq!(data.as_bytes().as_ptr(), ptr);
+    assert_eq!(data.as_bytes(), &[1, 2, 3, 4]);
+    assert!(Data::from_vec(Vec::new()).is_empty());
+}
+
+#[test]
+fn data_with_release_calls_release_on_drop() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    static BYTES: [u8; 3] = [1, 2, 3];
+    let released = Arc::new(AtomicBool::new(false));
+    let data = {
+        let released = released.clone();
+        unsafe {
+            Data::new_bytes_with_release(&BYTES, move || released.store(true, Ordering::SeqCst))
+        }
+    };
+    let data2 = data.clone();
+    drop(data);
+    assert!(!released.load(Ordering::SeqCst));
+    assert_eq!(data2.as_bytes(), &BYTES);
+    drop(data2);
+    assert!(released.load(Ordering::SeqCst));
+}
+
+#[test]
+fn data_uninitialized_can_be_written() {
+    let mut data = unsafe { Data::new_uninitialized(3) };
+    unsafe { data.writable_data() }.copy_from_slice(&[4, 5, 6]);
+    assert!(data == Data::from_static(&[4, 5, 6]));
+}
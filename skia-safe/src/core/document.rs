@@ -1,14 +1,15 @@
 use crate::interop::DynamicMemoryWStream;
 use crate::prelude::*;
-use crate::{Canvas, Data, Rect, Size};
+use crate::{Canvas, Data, OwnedSerialProcs, Rect, Size};
 use skia_bindings::{SkDocument, SkRefCntBase};
 use std::pin::Pin;
 
 pub struct Document<State = state::Open> {
     // note: order matters here, first the document must be
-    // dropped _and then_ the stream.
+    // dropped _and then_ the stream and the serial procs.
     document: RCHandle<SkDocument>,
     stream: Pin<Box<DynamicMemoryWStream>>,
+    serial_procs: Option<OwnedSerialProcs>,
 
     state: State,
 }
@@ -43,10 +44,20 @@ impl Document {
     pub(crate) fn new(
         stream: Pin<Box<DynamicMemoryWStream>>,
         document: RCHandle<SkDocument>,
+    ) -> Self {
+        Self::new_with_serial_procs(stream, document, None)
+    }
+
+    /// Creates a document that refers to `serial_procs`, for example to serialize its pages.
+    pub(crate) fn new_with_serial_procs(
+        stream: Pin<Box<DynamicMemoryWStream>>,
+        document: RCHandle<SkDocument>,
+        serial_procs: Option<OwnedSerialProcs>,
     ) -> Self {
         Document {
             document,
             stream,
+            serial_procs,
             state: state::Open { pages: 0 },
         }
     }
@@ -75,6 +86,7 @@ impl Document {
         Document {
             stream: self.stream,
             document: self.document,
+            serial_procs: self.serial_procs,
             state: state::OnPage {
                 canvas,
                 page: self.state.pages + 1,
@@ -114,6 +126,7 @@ impl Document<state::OnPage> {
         Document {
             stream: self.stream,
             document: self.document,
+            serial_procs: self.serial_procs,
             state: state::Open {
                 pages: self.state.page,
             },
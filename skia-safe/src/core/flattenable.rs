@@ -2,15 +2,178 @@ use crate::prelude::*;
 use crate::Data;
 use skia_bindings as sb;
 use skia_bindings::SkFlattenable;
-use std::ffi::CStr;
+use std::ffi::{c_void, CStr, CString};
 
-// TODO: getFactory()?
-// TODO: NameToFactory()?
-// TODO: FactoryToName()?
-// TODO: Register()?
-// TODO: getFlattenableType()?
 // TODO: serialize() with SkSerialProcs?
 
+pub use skia_bindings::SkFlattenable_Type as FlattenableType;
+#[test]
+fn test_flattenable_type_naming() {
+    let _ = FlattenableType::SkShaderBase;
+}
+
+/// A function registered with Skia that recreates a flattenable from its serialized form.
+///
+/// Only factories of Skia's own flattenables can be looked up and registered. Skia calls a
+/// factory without any context and passes it the internals of its private `SkReadBuffer`, so
+/// factories implemented in Rust are not supported.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Factory(*mut c_void);
+
+unsafe impl Send for Factory {}
+unsafe impl Sync for Factory {}
+
+impl Factory {
+    /// Looks up the factory that is registered under `name`.
+    pub fn from_name(name: impl AsRef<str>) -> Option<Factory> {
+        let name = CString::new(name.as_ref()).ok()?;
+        let factory = unsafe { sb::C_SkFlattenable_NameToFactory(name.as_ptr()) };
+        if !factory.is_null() {
+            Some(Factory(factory))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the name the factory is registered under, or `None` if it is not registered.
+    pub fn name(self) -> Option<&'static CStr> {
+        let name = unsafe { sb::C_SkFlattenable_FactoryToName(self.0) };
+        if !name.is_null() {
+            Some(unsafe { CStr::from_ptr(name) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the sorted names of the built-in flattenables of Skia m81 that resolve to a
+    /// factory in this build.
+    ///
+    /// This is a list the bindings maintain, not an enumeration of Skia's registry, which Skia
+    /// does not offer. Factories that are registered under other names, for example with
+    /// `register()`, are not included.
+    pub fn known_builtin_names() -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = BUILTIN_NAMES
+            .iter()
+            .cloned()
+            .filter(|name| Factory::from_name(name).is_some())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Registers the factory under an additional `name`, for example to accept data that was
+    /// serialized under a legacy name.
+    ///
+    /// The name is leaked, Skia keeps a reference to it for the lifetime of the process.
+    ///
+    /// # Safety
+    ///
+    /// Skia sorts its registry when it is first used, so this must be called before any
+    /// flattenable is serialized, deserialized, or looked up. Skia's registry has room for a
+    /// fixed number of entries (128 in m81) and does not check for overflow in release builds.
+    pub unsafe fn register(self, name: impl AsRef<str>) {
+        let name = CString::new(name.as_ref()).unwrap();
+        let name: &'static CStr = Box::leak(name.into_boxed_c_str());
+        sb::C_SkFlattenable_Register(name.as_ptr(), self.0);
+    }
+}
+
+/// The names Skia's built-in flattenables are registered under in m81, including the legacy
+/// names it keeps to read older data.
+const BUILTIN_NAMES: &[&str] = &[
+    // shaders
+    "SkBitmapProcShader",
+    "SkColor4Shader",
+    "SkColorFilterShader",
+    "SkColorShader",
+    "SkComposeShader",
+    "SkEmptyShader",
+    "SkImageShader",
+    "SkLightingShaderImpl",
+    "SkLinearGradient",
+    "SkLocalMatrixShader",
+    "SkPerlinNoiseShaderImpl",
+    "SkPictureShader",
+    "SkRTShader",
+    "SkRadialGradient",
+    "SkShader_Blend",
+    "SkShader_Lerp",
+    "SkSweepGradient",
+    "SkTwoPointConicalGradient",
+    // color filters
+    "SkColorFilter_Matrix",
+    "SkComposeColorFilter",
+    "SkHighContrast_Filter",
+    "SkLumaColorFilter",
+    "SkMixerColorFilter",
+    "SkModeColorFilter",
+    "SkOverdrawColorFilter",
+    "SkRuntimeColorFilter",
+    "SkSRGBGammaColorFilter",
+    "SkTable_ColorFilter",
+    // mask filters
+    "SkBlurMaskFilterImpl",
+    "SkCombineMF",
+    "SkEmbossMaskFilter",
+    "SkMatrixMF",
+    "SkShaderMF",
+    "SkTableMF",
+    // path effects
+    "SkComposePathEffect",
+    "SkCornerPathEffect",
+    "SkDashImpl",
+    "SkDiscretePathEffect",
+    "SkLine2DPathEffect",
+    "SkMatrixPE",
+    "SkOpPE",
+    "SkPath1DPathEffect",
+    "SkPath2DPathEffect",
+    "SkStrokePE",
+    "SkSumPathEffect",
+    "SkTrimPE",
+    // image filters
+    "SkAlphaThresholdFilterImpl",
+    "SkArithmeticImageFilterImpl",
+    "SkBlurImageFilter",
+    "SkBlurImageFilterImpl",
+    "SkColorFilterImageFilter",
+    "SkColorFilterImageFilterImpl",
+    "SkComposeImageFilter",
+    "SkComposeImageFilterImpl",
+    "SkDiffuseLightingImageFilter",
+    "SkDilateImageFilter",
+    "SkDisplacementMapEffect",
+    "SkDisplacementMapEffectImpl",
+    "SkDropShadowImageFilter",
+    "SkDropShadowImageFilterImpl",
+    "SkErodeImageFilter",
+    "SkImageSource",
+    "SkImageSourceImpl",
+    "SkLocalMatrixImageFilter",
+    "SkMagnifierImageFilterImpl",
+    "SkMatrixConvolutionImageFilter",
+    "SkMatrixConvolutionImageFilterImpl",
+    "SkMatrixImageFilter",
+    "SkMergeImageFilter",
+    "SkMergeImageFilterImpl",
+    "SkMorphologyImageFilterImpl",
+    "SkOffsetImageFilter",
+    "SkOffsetImageFilterImpl",
+    "SkPaintImageFilter",
+    "SkPaintImageFilterImpl",
+    "SkPictureImageFilter",
+    "SkPictureImageFilterImpl",
+    "SkSpecularLightingImageFilter",
+    "SkTileImageFilter",
+    "SkTileImageFilterImpl",
+    "SkXfermodeImageFilterImpl",
+    "SkXfermodeImageFilter_Base",
+    // draw loopers
+    "SkLayerDrawLooper",
+    // drawables
+    "SkRecordedDrawable",
+];
+
 pub trait Flattenable: Sized {
     fn type_name(&self) -> &CStr;
     fn serialize(&self) -> Data;
@@ -44,3 +207,53 @@ where
         RCHandle::from_ptr(N::native_deserialize(data))
     }
 }
+
+impl<N> RCHandle<N>
+where
+    N: NativeFlattenable + NativeRefCountedBase,
+{
+    /// Returns the base type of the flattenable, for example `SkShaderBase` for all shaders.
+    pub fn flattenable_type(&self) -> FlattenableType {
+        unsafe { sb::C_SkFlattenable_getFlattenableType(self.native().native_flattenable()) }
+    }
+
+    /// Returns the factory that recreates this flattenable when it is deserialized.
+    pub fn factory(&self) -> Factory {
+        Factory(unsafe { sb::C_SkFlattenable_getFactory(self.native().native_flattenable()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Factory, Flattenable, FlattenableType};
+    use crate::{color_filters, BlendMode, Color, ColorFilter, PathEffect, Shader};
+
+    #[test]
+    fn factory_lookup_round_trips() {
+        let filter = color_filters::blend(Color::RED, BlendMode::SrcOver).unwrap();
+        assert!(filter.flattenable_type() == FlattenableType::SkColorFilter);
+        let factory = Factory::from_name(filter.type_name().to_str().unwrap()).unwrap();
+        assert_eq!(factory, filter.factory());
+        assert_eq!(factory.name().unwrap(), filter.type_name());
+        assert!(Factory::from_name("NoSuchFlattenable").is_none());
+    }
+
+    #[test]
+    fn known_builtin_names_resolve() {
+        let names = Factory::known_builtin_names();
+        assert!(names.contains(&"SkModeColorFilter"));
+        assert!(names.windows(2).all(|w| w[0] < w[1]));
+        for name in names {
+            assert!(Factory::from_name(name).is_some());
+        }
+    }
+
+    #[test]
+    fn deserialize_rejects_unexpected_types() {
+        let filter = color_filters::blend(Color::RED, BlendMode::SrcOver).unwrap();
+        let data = filter.serialize();
+        assert!(ColorFilter::deserialize(&data).is_some());
+        assert!(Shader::deserialize(&data).is_none());
+        assert!(PathEffect::deserialize(&data).is_none());
+    }
+}
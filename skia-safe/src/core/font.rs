@@ -1,11 +1,13 @@
 use crate::prelude::*;
 use crate::{
-    scalar, FontHinting, FontMetrics, GlyphId, Paint, Path, Point, Rect, TextEncoding, Typeface,
-    Unichar,
+    scalar, ColorType, FontHinting, FontMetrics, GlyphId, IRect, Matrix, Paint, Path, Point, Rect,
+    TextEncoding, Typeface, Unichar, Vector,
 };
 use skia_bindings as sb;
-use skia_bindings::{SkFont, SkFont_PrivFlags};
-use std::ptr;
+use skia_bindings::{RustGlyphImage, SkFont, SkFont_PrivFlags, SkMatrix, SkPath};
+use std::ffi::c_void;
+use std::os::raw;
+use std::{ptr, slice};
 
 pub use skia_bindings::SkFont_Edging as Edging;
 #[test]
@@ -439,7 +441,130 @@ impl Handle<SkFont> {
         unsafe { self.native().getPath(glyph_id, path.native_mut()) }.if_true_some(path)
     }
 
-    // TODO: getPaths() (needs a function to be passed, but supports a context).
+    /// Calls `f` for each glyph in `glyphs` with the glyph's outline, or `None` if the glyph
+    /// has no outline, and the matrix that maps the outline from its unscaled font units into
+    /// the font's size.
+    pub fn get_paths<F>(&self, glyphs: &[GlyphId], mut f: F)
+    where
+        F: FnMut(GlyphId, Option<&Path>, &Matrix),
+    {
+        struct Context<'a, F> {
+            glyphs: slice::Iter<'a, GlyphId>,
+            f: &'a mut F,
+        }
+
+        unsafe extern "C" fn glyph_path_proc<F>(
+            path: *const SkPath,
+            matrix: *const SkMatrix,
+            ctx: *mut c_void,
+        ) where
+            F: FnMut(GlyphId, Option<&Path>, &Matrix),
+        {
+            let ctx = &mut *(ctx as *mut Context<F>);
+            let glyph_id = *ctx.glyphs.next().unwrap();
+            let path = path.into_option().map(|path| Path::from_native_ref(&*path));
+            (ctx.f)(glyph_id, path, Matrix::from_native_ref(&*matrix))
+        }
+
+        let mut ctx = Context {
+            glyphs: glyphs.iter(),
+            f: &mut f,
+        };
+
+        unsafe {
+            self.native().getPaths(
+                glyphs.as_ptr(),
+                glyphs.len().try_into().unwrap(),
+                Some(glyph_path_proc::<F>),
+                &mut ctx as *mut Context<F> as *mut c_void,
+            )
+        }
+    }
+
+    /// Rasterizes a single glyph, see `get_glyph_masks()`.
+    ///
+    /// Returns `None` if the glyph does not cover any pixels.
+    pub fn get_glyph_mask(
+        &self,
+        glyph_id: GlyphId,
+        subpixel_offset: impl Into<Option<Vector>>,
+        paint: Option<&Paint>,
+    ) -> Option<GlyphMask> {
+        self.get_glyph_masks(&[glyph_id], subpixel_offset, paint)
+            .pop()
+            .unwrap()
+    }
+
+    /// Rasterizes `glyphs` with Skia's scaler, the same way a raster canvas renders them with
+    /// the font's hinting and edging, positioned at `subpixel_offset` relative to the pixel grid.
+    ///
+    /// Outline glyphs result in `ColorType::Alpha8` coverage masks, aliased glyphs are expanded to
+    /// 0 or 255. The colors of bitmap and COLR glyphs are only available as premultiplied
+    /// `ColorType::n32()` images, and LCD masks are `ColorType::RGB565`.
+    ///
+    /// `subpixel_offset` has no effect unless subpixel positioning is enabled with
+    /// `set_subpixel()`, and is then quantized by Skia.
+    ///
+    /// Returns `None` for glyphs that do not cover any pixels.
+    pub fn get_glyph_masks(
+        &self,
+        glyphs: &[GlyphId],
+        subpixel_offset: impl Into<Option<Vector>>,
+        paint: Option<&Paint>,
+    ) -> Vec<Option<GlyphMask>> {
+        struct Context<'a> {
+            glyphs: &'a [GlyphId],
+            masks: Vec<Option<GlyphMask>>,
+        }
+
+        unsafe extern "C" fn visit_glyph_image(
+            ctx: *mut c_void,
+            index: raw::c_int,
+            image: *const RustGlyphImage,
+        ) {
+            let ctx = &mut *(ctx as *mut Context);
+            let mask = image.as_ref().map(|image| {
+                let bounds = IRect::from_native_ref(&image.bounds);
+                let color_type = ColorType::from_native(image.colorType);
+                let row_len = bounds.width() as usize * color_type.bytes_per_pixel();
+                let pixels = (0..bounds.height() as usize)
+                    .flat_map(|row| {
+                        slice::from_raw_parts(
+                            (image.pixels as *const u8).add(row * image.rowBytes),
+                            row_len,
+                        )
+                        .iter()
+                        .cloned()
+                    })
+                    .collect();
+                GlyphMask {
+                    glyph_id: ctx.glyphs[index as usize],
+                    bounds: *bounds,
+                    color_type,
+                    row_bytes: row_len,
+                    pixels,
+                }
+            });
+            ctx.masks.push(mask);
+        }
+
+        let mut ctx = Context {
+            glyphs,
+            masks: Vec::with_capacity(glyphs.len()),
+        };
+        unsafe {
+            sb::C_SkFont_visitGlyphImages(
+                self.native(),
+                paint.native_ptr_or_null(),
+                glyphs.as_ptr(),
+                glyphs.len().try_into().unwrap(),
+                subpixel_offset.into().unwrap_or_default().into_native(),
+                &mut ctx as *mut Context as *mut c_void,
+                Some(visit_glyph_image),
+            )
+        }
+        ctx.masks
+    }
 
     pub fn metrics(&self) -> (scalar, FontMetrics) {
         let mut line_spacing = 0.0;
@@ -453,6 +578,39 @@ impl Handle<SkFont> {
     }
 }
 
+/// A glyph image rendered by `Font::get_glyph_masks()`.
+#[derive(Clone, Debug)]
+pub struct GlyphMask {
+    pub glyph_id: GlyphId,
+    /// The pixel bounds of the mask relative to the glyph's origin. `left` and `top` are the
+    /// horizontal and vertical bearings.
+    pub bounds: IRect,
+    pub color_type: ColorType,
+    pub row_bytes: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl GlyphMask {
+    pub fn width(&self) -> i32 {
+        self.bounds.width()
+    }
+
+    pub fn height(&self) -> i32 {
+        self.bounds.height()
+    }
+
+    /// Returns the pixels of row `y`, without the padding at the end of the row, or `None` if
+    /// `y` is not less than `height()`.
+    pub fn row(&self, y: usize) -> Option<&[u8]> {
+        if y >= self.height() as usize {
+            return None;
+        }
+        let start = y * self.row_bytes;
+        let len = self.width() as usize * self.color_type.bytes_per_pixel();
+        Some(&self.pixels[start..start + len])
+    }
+}
+
 #[test]
 fn test_flags() {
     let mut font = Font::new(Typeface::default(), 10.0);
@@ -467,3 +625,32 @@ fn test_flags() {
     font.set_embolden(false);
     assert!(!font.is_embolden());
 }
+
+#[test]
+fn test_get_paths() {
+    let font = Font::new(Typeface::default(), 12.0);
+    let glyphs = font.str_to_glyphs_vec("A B");
+    let mut visited = Vec::new();
+    font.get_paths(&glyphs, |glyph_id, path, _matrix| {
+        visited.push((glyph_id, path.is_some()))
+    });
+    assert_eq!(visited.len(), glyphs.len());
+    assert_eq!(visited[0], (glyphs[0], true));
+    assert_eq!(visited[1].0, glyphs[1]);
+}
+
+#[test]
+fn test_get_glyph_masks() {
+    let font = Font::new(Typeface::default(), 24.0);
+    let glyphs = font.str_to_glyphs_vec("O ");
+    let masks = font.get_glyph_masks(&glyphs, Vector::new(0.5, 0.0), None);
+    assert_eq!(masks.len(), 2);
+    let o = masks[0].as_ref().unwrap();
+    assert!(o.color_type == ColorType::Alpha8);
+    assert!(o.bounds.top < 0);
+    assert_eq!(o.pixels.len(), o.row_bytes * o.height() as usize);
+    let middle = o.row(o.height() as usize / 2).unwrap();
+    assert!(middle.iter().any(|a| *a != 0));
+    assert!(o.row(o.height() as usize).is_none());
+    assert!(masks[1].is_none());
+}
@@ -1,5 +1,9 @@
-use skia_bindings::SkGraphics;
+use crate::prelude::*;
+use crate::{trace_memory_dump, Data, ImageGenerator, TraceMemoryDump};
+use skia_bindings as sb;
+use skia_bindings::{SkData, SkGraphics, SkImageGenerator};
 use std::ffi::CString;
+use std::sync::{Arc, RwLock};
 
 pub fn init() {
     unsafe { SkGraphics::Init() };
@@ -77,7 +81,11 @@ pub fn set_resource_cache_single_allocation_byte_limit(new_limit: Option<usize>)
     }
 }
 
-// TODO: dump_memory_statistics() (needs SkTraceMemoryDumpWrapper interop wrapper).
+pub fn dump_memory_statistics(dump: &mut dyn TraceMemoryDump) {
+    trace_memory_dump::with_native_param(dump, |param| unsafe {
+        sb::C_SkGraphics_DumpMemoryStatistics(param)
+    })
+}
 
 pub fn purge_all_caches() {
     unsafe { SkGraphics::PurgeAllCaches() }
@@ -88,4 +96,88 @@ pub fn set_flags(flags: impl AsRef<str>) {
     unsafe { SkGraphics::SetFlags(c_str.as_ptr()) }
 }
 
-// TODO: ImageGeneratorFromEncodedDataFactory
+type ImageGeneratorFactory = Arc<dyn Fn(Data) -> Option<ImageGenerator> + Send + Sync>;
+
+lazy_static! {
+    static ref IMAGE_GENERATOR_FACTORY: RwLock<Option<ImageGeneratorFactory>> = RwLock::new(None);
+}
+
+/// Sets the factory that is asked first for an image generator whenever Skia decodes encoded
+/// data, for example in `Image::from_encoded()` or when pictures are deserialized.
+///
+/// If the factory returns `None`, Skia uses its own codecs.
+pub fn set_image_generator_from_encoded_data_factory(
+    factory: impl Fn(Data) -> Option<ImageGenerator> + Send + Sync + 'static,
+) {
+    *IMAGE_GENERATOR_FACTORY.write().unwrap() = Some(Arc::new(factory));
+    unsafe {
+        sb::C_SkGraphics_SetImageGeneratorFromEncodedDataFactory(Some(image_generator_factory))
+    }
+}
+
+/// Removes the factory set with `set_image_generator_from_encoded_data_factory()`.
+pub fn reset_image_generator_from_encoded_data_factory() {
+    unsafe { sb::C_SkGraphics_SetImageGeneratorFromEncodedDataFactory(None) }
+    *IMAGE_GENERATOR_FACTORY.write().unwrap() = None;
+}
+
+unsafe extern "C" fn image_generator_factory(data: *mut SkData) -> *mut SkImageGenerator {
+    let data = Data::from_ptr(data).unwrap();
+    // don't hold the lock while the factory runs, it may decode encoded data itself.
+    let factory = IMAGE_GENERATOR_FACTORY.read().unwrap().clone();
+    match factory {
+        Some(factory) => factory(data).map_or(std::ptr::null_mut(), |mut generator| {
+            let ptr = generator.native_mut() as *mut SkImageGenerator;
+            std::mem::forget(generator);
+            ptr
+        }),
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[test]
+#[serial_test::serial]
+fn decode_custom_format_with_rust_image_generator() {
+    use crate::ImageGeneratorImpl;
+    use crate::{AlphaType, ColorType, Image, ImageInfo};
+
+    // A format of the magic bytes `GRAY` and one byte of gray level for a 4x4 image.
+    struct GrayGenerator(u8);
+
+    impl ImageGeneratorImpl for GrayGenerator {
+        fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool {
+            if info.color_type() != ColorType::Gray8 {
+                return false;
+            }
+            for row in pixels.chunks_mut(row_bytes) {
+                let width = info.width() as usize;
+                row[..width].iter_mut().for_each(|p| *p = self.0)
+            }
+            true
+        }
+    }
+
+    set_image_generator_from_encoded_data_factory(|data| {
+        if data.len() != 5 || &data[..4] != b"GRAY" {
+            return None;
+        }
+        let info = ImageInfo::new((4, 4), ColorType::Gray8, AlphaType::Opaque, None);
+        Some(ImageGenerator::from_impl(&info, GrayGenerator(data[4])))
+    });
+
+    let image = Image::from_encoded(Data::new_copy(b"GRAY\x80"), None).unwrap();
+    assert_eq!(image.width(), 4);
+    let info = ImageInfo::new((4, 4), ColorType::Gray8, AlphaType::Opaque, None);
+    let mut pixels = [0u8; 16];
+    assert!(image.read_pixels(
+        &info,
+        &mut pixels,
+        4,
+        (0, 0),
+        crate::image::CachingHint::Allow
+    ));
+    assert_eq!(pixels, [0x80; 16]);
+
+    reset_image_generator_from_encoded_data_factory();
+    assert!(Image::from_encoded(Data::new_copy(b"GRAY\x80"), None).is_none());
+}
@@ -3,11 +3,12 @@ use crate::gpu;
 use crate::prelude::*;
 use crate::{
     AlphaType, Bitmap, ColorSpace, ColorType, Data, EncodedImageFormat, IPoint, IRect, ISize,
-    ImageInfo, Matrix, Paint, Picture, Shader, TileMode,
+    ImageInfo, Matrix, Paint, Picture, Pixel, Shader, TileMode,
 };
 use crate::{FilterQuality, ImageFilter, ImageGenerator, Pixmap};
 use skia_bindings as sb;
 use skia_bindings::{SkImage, SkRefCntBase};
+use std::ffi::c_void;
 use std::mem;
 
 pub use skia_bindings::SkImage_BitDepth as BitDepth;
@@ -49,7 +50,44 @@ impl RCHandle<SkImage> {
         })
     }
 
-    // TODO: MakeFromRaster()
+    /// Creates an image that takes ownership of the pixels without copying them. The pixels are
+    /// dropped when the image is freed, which may happen on another thread.
+    ///
+    /// Returns `None` if `P` does not match the color type of `info`, or if the pixels do not
+    /// cover `info` with the given `row_bytes`.
+    pub fn from_raster_with_release<P: Pixel + Send + 'static>(
+        info: &ImageInfo,
+        pixels: impl Into<Box<[P]>>,
+        row_bytes: usize,
+    ) -> Option<Image> {
+        unsafe extern "C" fn release_raster_pixels<P>(_pixels: *const c_void, ctx: *mut c_void) {
+            drop(Box::from_raw(ctx as *mut Box<[P]>))
+        }
+
+        let pixels = pixels.into();
+        if !P::matches_color_type(info.color_type())
+            || info.compute_byte_size(row_bytes) > pixels.len() * mem::size_of::<P>()
+        {
+            return None;
+        }
+
+        let addr = pixels.as_ptr();
+        let pixels = Box::into_raw(Box::new(pixels));
+        let image = Image::from_ptr(unsafe {
+            sb::C_SkImage_MakeFromRaster(
+                info.native(),
+                addr as _,
+                row_bytes,
+                Some(release_raster_pixels::<P>),
+                pixels as _,
+            )
+        });
+        // Skia calls the release proc only if it created the image.
+        if image.is_none() {
+            drop(unsafe { Box::from_raw(pixels) });
+        }
+        image
+    }
 
     pub fn from_bitmap(bitmap: &Bitmap) -> Option<Image> {
         Image::from_ptr(unsafe { sb::C_SkImage_MakeFromBitmap(bitmap.native()) })
@@ -637,3 +675,19 @@ impl RCHandle<SkImage> {
         })
     }
 }
+
+#[test]
+fn test_from_raster_with_release() {
+    let info = ImageInfo::new_n32_premul((2, 2), None);
+    assert!(Image::from_raster_with_release(&info, vec![0u8; 16], 8).is_none());
+    assert!(Image::from_raster_with_release(&info, vec![0u32; 3], 8).is_none());
+
+    // opaque gray, so that the test does not depend on the N32 byte order.
+    let gray = 0xff80_8080u32;
+    let image = Image::from_raster_with_release(&info, vec![gray; 6], 12).unwrap();
+    assert_eq!(image.dimensions(), ISize::new(2, 2));
+
+    let mut pixels = [0u32; 4];
+    assert!(image.read_pixels(&info, &mut pixels, 8, (0, 0), CachingHint::Allow));
+    assert_eq!(pixels, [gray; 4]);
+}
@@ -6,11 +6,26 @@ use crate::{
     YUVColorSpace,
 };
 use skia_bindings as sb;
-use skia_bindings::SkImageGenerator;
+use skia_bindings::{RustImageGeneratorParam, SkImageGenerator};
 use std::ffi::c_void;
 
 pub type ImageGenerator = RefHandle<SkImageGenerator>;
 
+/// A Rust implementation of an image generator, see `ImageGenerator::from_impl()`.
+///
+/// Skia may call the generator from other threads, but never concurrently.
+pub trait ImageGeneratorImpl: Send {
+    /// Returns the encoded data the generator decodes, if it is available.
+    fn encoded_data(&mut self) -> Option<Data> {
+        None
+    }
+
+    /// Decodes the pixels into `pixels`. `info` has the dimensions of the generator, but may
+    /// request another color type or color space. Returns `false` if the conversion is not
+    /// supported or decoding failed.
+    fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool;
+}
+
 impl NativeDrop for SkImageGenerator {
     fn drop(&mut self) {
         unsafe { sb::C_SkImageGenerator_delete(self) }
@@ -110,6 +125,18 @@ impl RefHandle<SkImageGenerator> {
         unsafe { sb::C_SkImageGenerator_texturesAreCacheable(self.native()) }
     }
 
+    /// Creates a generator of an image described by `info` that forwards to a Rust
+    /// implementation.
+    pub fn from_impl<T: ImageGeneratorImpl + 'static>(info: &ImageInfo, generator: T) -> Self {
+        let param = RustImageGeneratorParam {
+            ctx: Box::into_raw(Box::new(generator)) as _,
+            refEncodedData: Some(rust_image_generator::ref_encoded_data::<T>),
+            getPixels: Some(rust_image_generator::get_pixels::<T>),
+            drop: Some(rust_image_generator::drop::<T>),
+        };
+        Self::from_ptr(unsafe { sb::C_RustImageGenerator_new(info.native(), &param) }).unwrap()
+    }
+
     pub fn from_encoded(encoded: Data) -> Option<Self> {
         Self::from_ptr(unsafe { sb::C_SkImageGenerator_MakeFromEncoded(encoded.into_ptr()) })
     }
@@ -134,3 +161,34 @@ impl RefHandle<SkImageGenerator> {
         })
     }
 }
+
+mod rust_image_generator {
+    use super::ImageGeneratorImpl;
+    use crate::prelude::*;
+    use crate::ImageInfo;
+    use skia_bindings::{SkData, SkImageInfo};
+    use std::ffi::c_void;
+    use std::slice;
+
+    pub unsafe extern "C" fn ref_encoded_data<T: ImageGeneratorImpl>(
+        ctx: *mut c_void,
+    ) -> *mut SkData {
+        (*(ctx as *mut T)).encoded_data().into_ptr_or_null()
+    }
+
+    pub unsafe extern "C" fn get_pixels<T: ImageGeneratorImpl>(
+        ctx: *mut c_void,
+        info: *const SkImageInfo,
+        pixels: *mut c_void,
+        row_bytes: usize,
+    ) -> bool {
+        let info = ImageInfo::from_native_ref(&*info);
+        let len = info.compute_byte_size(row_bytes);
+        let pixels = slice::from_raw_parts_mut(pixels as *mut u8, len);
+        (*(ctx as *mut T)).get_pixels(info, pixels, row_bytes)
+    }
+
+    pub unsafe extern "C" fn drop<T>(ctx: *mut c_void) {
+        std::mem::drop(Box::from_raw(ctx as *mut T))
+    }
+}
@@ -180,6 +180,45 @@ impl<'a> Iterator for RawIter<'a> {
     }
 }
 
+/// A segment of a path, without the start point that is implied by the previous segment.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Segment {
+    MoveTo(Point),
+    LineTo(Point),
+    QuadTo(Point, Point),
+    ConicTo { p1: Point, p2: Point, w: scalar },
+    CubicTo(Point, Point, Point),
+    Close,
+}
+
+/// Iterates the segments of a path as they were added, see `Path::segments()`.
+pub struct Segments<'a>(RawIter<'a>);
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut points = [Point::default(); Verb::MAX_POINTS];
+        let verb = SkPathVerb::from_native(unsafe {
+            sb::C_SkPath_RawIter_next(self.0.native_mut(), points.native_mut().as_mut_ptr())
+        });
+        let segment = match verb {
+            Verb::Move => Segment::MoveTo(points[0]),
+            Verb::Line => Segment::LineTo(points[1]),
+            Verb::Quad => Segment::QuadTo(points[1], points[2]),
+            Verb::Conic => Segment::ConicTo {
+                p1: points[1],
+                p2: points[2],
+                w: self.0.conic_weight().unwrap(),
+            },
+            Verb::Cubic => Segment::CubicTo(points[1], points[2], points[3]),
+            Verb::Close => Segment::Close,
+            Verb::Done => return None,
+        };
+        Some(segment)
+    }
+}
+
 pub type Path = Handle<SkPath>;
 
 impl NativeDrop for SkPath {
@@ -206,11 +245,39 @@ impl Default for Handle<SkPath> {
     }
 }
 
+impl Extend<Segment> for Handle<SkPath> {
+    fn extend<I: IntoIterator<Item = Segment>>(&mut self, segments: I) {
+        for segment in segments {
+            match segment {
+                Segment::MoveTo(p) => self.move_to(p),
+                Segment::LineTo(p) => self.line_to(p),
+                Segment::QuadTo(p1, p2) => self.quad_to(p1, p2),
+                Segment::ConicTo { p1, p2, w } => self.conic_to(p1, p2, w),
+                Segment::CubicTo(p1, p2, p3) => self.cubic_to(p1, p2, p3),
+                Segment::Close => self.close(),
+            };
+        }
+    }
+}
+
 impl Handle<SkPath> {
     pub fn new() -> Self {
         Self::from_native(unsafe { SkPath::new() })
     }
 
+    /// Creates a path from segments, the inverse of `segments()`.
+    pub fn from_segments(segments: impl IntoIterator<Item = Segment>) -> Self {
+        let mut path = Path::new();
+        path.extend(segments);
+        path
+    }
+
+    /// Returns an iterator over the segments of the path, including all moves and closes, and
+    /// without allocating.
+    pub fn segments(&self) -> Segments {
+        Segments(RawIter::new(self))
+    }
+
     pub fn is_interpolatable(&self, compare: &Path) -> bool {
         unsafe { self.native().isInterpolatable(compare.native()) }
     }
@@ -925,3 +992,28 @@ fn is_volatile() {
     p.set_is_volatile(true);
     assert!(p.is_volatile());
 }
+
+#[test]
+fn segments_round_trip() {
+    let segments = [
+        Segment::MoveTo(Point::new(0.0, 0.0)),
+        Segment::LineTo(Point::new(10.0, 0.0)),
+        Segment::QuadTo(Point::new(15.0, 5.0), Point::new(10.0, 10.0)),
+        Segment::ConicTo {
+            p1: Point::new(5.0, 15.0),
+            p2: Point::new(0.0, 10.0),
+            w: 0.5,
+        },
+        Segment::Close,
+        Segment::MoveTo(Point::new(20.0, 20.0)),
+        Segment::CubicTo(
+            Point::new(25.0, 20.0),
+            Point::new(30.0, 25.0),
+            Point::new(30.0, 30.0),
+        ),
+    ];
+    let path = Path::from_segments(segments.iter().cloned());
+    assert_eq!(path.count_verbs(), segments.len());
+    let read: Vec<Segment> = path.segments().collect();
+    assert_eq!(read, segments);
+}
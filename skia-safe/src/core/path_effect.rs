@@ -2,7 +2,8 @@ use crate::prelude::*;
 use crate::{scalar, Matrix, NativeFlattenable, Path, Point, Rect, StrokeRec, Vector};
 use skia_bindings as sb;
 use skia_bindings::{
-    SkFlattenable, SkPathEffect, SkPathEffect_DashType, SkPathEffect_PointData, SkRefCntBase,
+    RustPathEffectParam, SkFlattenable, SkPathEffect, SkPathEffect_DashType,
+    SkPathEffect_PointData, SkRefCntBase,
 };
 use std::os::raw;
 use std::slice;
@@ -74,6 +75,27 @@ pub struct DashInfo {
 
 pub type PathEffect = RCHandle<SkPathEffect>;
 
+/// A Rust implementation of a path effect, see `PathEffect::from_impl()`.
+///
+/// Path effects are shared and may be used from multiple threads at the same time.
+pub trait PathEffectImpl: Send + Sync {
+    /// Writes the effect applied to `src` into `dst`, which is empty, and may modify
+    /// `stroke_rec`, for example to turn a stroke into a fill. Returns `false` if the effect
+    /// does not apply, in which case `src` is drawn unmodified.
+    fn filter_path(
+        &self,
+        dst: &mut Path,
+        src: &Path,
+        stroke_rec: &mut StrokeRec,
+        cull_rect: Option<&Rect>,
+    ) -> bool;
+
+    /// Returns conservative bounds of the effect applied to a path with the bounds `src`.
+    fn compute_fast_bounds(&self, src: &Rect) -> Rect {
+        *src
+    }
+}
+
 impl NativeBase<SkRefCntBase> for SkPathEffect {}
 impl NativeBase<SkFlattenable> for SkPathEffect {}
 
@@ -92,6 +114,20 @@ impl NativeFlattenable for SkPathEffect {
 }
 
 impl RCHandle<SkPathEffect> {
+    /// Creates a path effect that forwards to a Rust implementation.
+    ///
+    /// The effect is serialized without its state, for example as part of a `Picture`, and is
+    /// deserialized as no path effect.
+    pub fn from_impl<T: PathEffectImpl + 'static>(effect: T) -> PathEffect {
+        let param = RustPathEffectParam {
+            ctx: Box::into_raw(Box::new(effect)) as _,
+            filterPath: Some(rust_path_effect::filter_path::<T>),
+            computeFastBounds: Some(rust_path_effect::compute_fast_bounds::<T>),
+            drop: Some(rust_path_effect::drop::<T>),
+        };
+        PathEffect::from_ptr(unsafe { sb::C_RustPathEffect_new(&param) }).unwrap()
+    }
+
     pub fn sum(first: PathEffect, second: PathEffect) -> PathEffect {
         PathEffect::from_ptr(unsafe {
             sb::C_SkPathEffect_MakeSum(first.into_ptr(), second.into_ptr())
@@ -193,8 +229,155 @@ impl RCHandle<SkPathEffect> {
     }
 }
 
+mod rust_path_effect {
+    use super::PathEffectImpl;
+    use crate::prelude::*;
+    use crate::{Path, Rect, StrokeRec};
+    use skia_bindings::{SkPath, SkRect, SkStrokeRec};
+    use std::ffi::c_void;
+
+    pub unsafe extern "C" fn filter_path<T: PathEffectImpl>(
+        ctx: *mut c_void,
+        dst: *mut SkPath,
+        src: *const SkPath,
+        stroke_rec: *mut SkStrokeRec,
+        cull_rect: *const SkRect,
+    ) -> bool {
+        let cull_rect = if cull_rect.is_null() {
+            None
+        } else {
+            Some(Rect::from_native_ref(&*cull_rect))
+        };
+        (*(ctx as *const T)).filter_path(
+            Path::from_native_ref_mut(&mut *dst),
+            Path::from_native_ref(&*src),
+            StrokeRec::from_native_ref_mut(&mut *stroke_rec),
+            cull_rect,
+        )
+    }
+
+    pub unsafe extern "C" fn compute_fast_bounds<T: PathEffectImpl>(
+        ctx: *mut c_void,
+        dst: *mut SkRect,
+        src: *const SkRect,
+    ) {
+        *dst = (*(ctx as *const T))
+            .compute_fast_bounds(Rect::from_native_ref(&*src))
+            .into_native();
+    }
+
+    pub unsafe extern "C" fn drop<T>(ctx: *mut c_void) {
+        std::mem::drop(Box::from_raw(ctx as *mut T))
+    }
+}
+
 #[test]
 fn create_and_drop_point_data() {
     let data = PointData::default();
     drop(data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PathEffectImpl;
+    use crate::{
+        dash_path_effect, paint, Color, Flattenable, Paint, Path, PathEffect, Picture,
+        PictureRecorder, Rect, StrokeRec, Surface,
+    };
+
+    /// Replaces every path by the outline of its bounds, grown by `outset`.
+    struct Outline {
+        outset: f32,
+    }
+
+    impl PathEffectImpl for Outline {
+        fn filter_path(
+            &self,
+            dst: &mut Path,
+            src: &Path,
+            stroke_rec: &mut StrokeRec,
+            _cull_rect: Option<&Rect>,
+        ) -> bool {
+            stroke_rec.set_fill_style();
+            dst.add_rect(src.bounds().with_outset((self.outset, self.outset)), None);
+            true
+        }
+
+        fn compute_fast_bounds(&self, src: &Rect) -> Rect {
+            src.with_outset((self.outset, self.outset))
+        }
+    }
+
+    #[test]
+    fn rust_path_effect_filters_and_composes() {
+        let effect = PathEffect::from_impl(Outline { outset: 2.0 });
+        let mut src = Path::new();
+        src.add_rect(Rect::new(10.0, 10.0, 20.0, 20.0), None);
+        let (dst, stroke_rec) = effect
+            .filter_path(&src, &StrokeRec::new_hairline(), src.bounds())
+            .unwrap();
+        assert_eq!(*dst.bounds(), Rect::new(8.0, 8.0, 22.0, 22.0));
+        assert!(stroke_rec.is_fill_style());
+        assert_eq!(
+            effect.compute_fast_bounds(src.bounds()),
+            Rect::new(8.0, 8.0, 22.0, 22.0)
+        );
+
+        let dash = dash_path_effect::new(&[2.0, 2.0], 0.0).unwrap();
+        // dashes the rect first and then replaces the dashes by their outline.
+        let composed = PathEffect::compose(effect.clone(), dash.clone());
+        let summed = PathEffect::sum(dash, effect);
+
+        for effect in &[composed, summed] {
+            let (dst, stroke_rec) = effect
+                .filter_path(&src, &StrokeRec::new_hairline(), src.bounds())
+                .unwrap();
+            assert_eq!(*dst.bounds(), Rect::new(8.0, 8.0, 22.0, 22.0));
+            assert!(stroke_rec.is_fill_style());
+
+            let mut surface = Surface::new_raster_n32_premul((32, 32)).unwrap();
+            surface.canvas().clear(Color::WHITE);
+            let mut paint = Paint::default();
+            paint.set_style(paint::Style::Stroke);
+            paint.set_color(Color::BLACK);
+            paint.set_path_effect(effect.clone());
+            surface.canvas().draw_path(&src, &paint);
+            let pixmap = surface.peek_pixels().unwrap();
+            assert_eq!(pixmap.get_color((15, 15)), Color::BLACK);
+            assert_eq!(pixmap.get_color((4, 4)), Color::WHITE);
+        }
+    }
+
+    #[test]
+    fn rust_path_effects_deserialize_to_none() {
+        let effect = PathEffect::from_impl(Outline { outset: 2.0 });
+        assert_eq!(
+            effect.factory().name().unwrap().to_str().unwrap(),
+            "RustPathEffect"
+        );
+        let data = effect.serialize();
+        assert!(PathEffect::deserialize(&data).is_none());
+    }
+
+    #[test]
+    fn pictures_with_rust_path_effects_serialize() {
+        let mut recorder = PictureRecorder::new();
+        let bounds = Rect::new(0.0, 0.0, 32.0, 32.0);
+        let canvas = recorder.begin_recording(bounds, None, None);
+        let mut paint = Paint::default();
+        paint.set_path_effect(PathEffect::from_impl(Outline { outset: 2.0 }));
+        canvas.draw_rect(Rect::new(10.0, 10.0, 20.0, 20.0), &paint);
+        let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+        let data = picture.serialize();
+        let picture = Picture::from_data(&data).unwrap();
+
+        // the effect is dropped, so the rect is drawn unmodified.
+        let mut surface = Surface::new_raster_n32_premul((32, 32)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        surface.canvas().draw_picture(&picture, None, None);
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((15, 15)), Color::BLACK);
+        assert_eq!(pixmap.get_color((9, 9)), Color::WHITE);
+    }
+}
@@ -1,8 +1,10 @@
 use crate::prelude::*;
-use crate::{Canvas, Data, Rect};
+use crate::{Canvas, Data, DeserialProcs, Rect, Region, SerialProcs};
 use crate::{Matrix, Shader, TileMode};
 use skia_bindings as sb;
 use skia_bindings::{SkPicture, SkRefCntBase};
+use std::ffi::c_void;
+use std::ops::Range;
 
 pub type Picture = RCHandle<SkPicture>;
 
@@ -13,8 +15,6 @@ impl NativeRefCountedBase for SkPicture {
 impl RCHandle<SkPicture> {
     // TODO: wrap MakeFromStream
 
-    // TODO: may support SkSerialProces in MakeFromData?
-
     pub fn from_data(data: &Data) -> Option<Picture> {
         Picture::from_ptr(unsafe { sb::C_SkPicture_MakeFromData(data.native()) })
     }
@@ -25,12 +25,73 @@ impl RCHandle<SkPicture> {
         })
     }
 
-    // TODO: AbortCallback and the function that use it.
+    pub fn from_data_with_procs(data: &Data, procs: &mut DeserialProcs) -> Option<Picture> {
+        Self::from_bytes_with_procs(data.as_bytes(), procs)
+    }
+
+    pub fn from_bytes_with_procs(bytes: &[u8], procs: &mut DeserialProcs) -> Option<Picture> {
+        let native_procs = procs.native_procs();
+        Picture::from_ptr(unsafe {
+            sb::C_SkPicture_MakeFromDataWithProcs(bytes.as_ptr() as _, bytes.len(), &native_procs)
+        })
+    }
 
     pub fn playback(&self, mut canvas: impl AsMut<Canvas>) {
         unsafe { sb::C_SkPicture_playback(self.native(), canvas.as_mut().native_mut()) }
     }
 
+    /// Replays the picture and calls `abort` between the drawing commands. If `abort` returns
+    /// `true`, the playback stops.
+    ///
+    /// Note that an aborted playback may leave the canvas with unbalanced save / restore
+    /// calls, so callers may want to wrap it in `Canvas::save()` and `Canvas::restore_to_count()`.
+    pub fn playback_with_abort<F>(&self, mut canvas: impl AsMut<Canvas>, mut abort: F)
+    where
+        F: FnMut() -> bool,
+    {
+        unsafe {
+            sb::C_SkPicture_playbackWithAbortCallback(
+                self.native(),
+                canvas.as_mut().native_mut(),
+                &mut abort as *mut F as *mut c_void,
+                Some(abort_callback::<F>),
+            )
+        }
+    }
+
+    /// Replays only the recorded drawing commands in `ops`. The indices refer to the commands
+    /// counted by `approximate_op_count()`.
+    ///
+    /// `ops` is clamped to the recorded commands. Returns `false` if the picture does not
+    /// support a partial replay.
+    pub fn playback_range(&self, mut canvas: impl AsMut<Canvas>, ops: Range<usize>) -> bool {
+        // clamped to the op count, which is an `int`, so the conversions below can not fail.
+        let end = ops.end.min(self.approximate_op_count());
+        let start = ops.start.min(end);
+        unsafe {
+            sb::C_SkPicture_partialPlayback(
+                self.native(),
+                canvas.as_mut().native_mut(),
+                start as i32,
+                end as i32,
+            )
+        }
+    }
+
+    /// Replays only the recorded drawing commands that intersect `region`, which is in the
+    /// device coordinates of `canvas`. Drawing is clipped to `region`.
+    ///
+    /// Commands outside of `region` are only skipped if the picture was recorded with a
+    /// `BBHFactory`, see `PictureRecorder::begin_recording()`. Otherwise all commands are
+    /// replayed and clipped.
+    pub fn playback_region(&self, mut canvas: impl AsMut<Canvas>, region: &Region) {
+        let canvas = canvas.as_mut();
+        let save_count = canvas.save();
+        canvas.clip_region(region, None);
+        self.playback(&mut *canvas);
+        canvas.restore_to_count(save_count);
+    }
+
     pub fn cull_rect(&self) -> Rect {
         Rect::from_native(unsafe { sb::C_SkPicture_cullRect(self.native()) })
     }
@@ -39,12 +100,16 @@ impl RCHandle<SkPicture> {
         unsafe { sb::C_SkPicture_uniqueID(self.native()) }
     }
 
-    // TODO: support SkSerialProcs in serialize()?
-
     pub fn serialize(&self) -> Data {
         Data::from_ptr(unsafe { sb::C_SkPicture_serialize(self.native()) }).unwrap()
     }
 
+    pub fn serialize_with_procs(&self, procs: &mut SerialProcs) -> Data {
+        let native_procs = procs.native_procs();
+        Data::from_ptr(unsafe { sb::C_SkPicture_serializeWithProcs(self.native(), &native_procs) })
+            .unwrap()
+    }
+
     pub fn new_placeholder(cull: impl AsRef<Rect>) -> Picture {
         Picture::from_ptr(unsafe { sb::C_SkPicture_MakePlaceholder(cull.as_ref().native()) })
             .unwrap()
@@ -58,6 +123,16 @@ impl RCHandle<SkPicture> {
         }
     }
 
+    /// Returns the approximate number of operations, including the operations of nested
+    /// pictures if `nested` is `true`.
+    pub fn approximate_op_count_nested(&self, nested: bool) -> usize {
+        unsafe {
+            sb::C_SkPicture_approximateOpCountNested(self.native(), nested)
+                .try_into()
+                .unwrap()
+        }
+    }
+
     pub fn approximate_bytes_used(&self) -> usize {
         unsafe {
             let mut value = 0;
@@ -90,3 +165,65 @@ impl RCHandle<SkPicture> {
         .unwrap()
     }
 }
+
+unsafe extern "C" fn abort_callback<F>(ctx: *mut c_void) -> bool
+where
+    F: FnMut() -> bool,
+{
+    (*(ctx as *mut F))()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, Paint, Picture, PictureRecorder, Rect, Surface};
+
+    fn record_rects(count: usize) -> Picture {
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 100.0, 100.0), None, None);
+        let paint = Paint::default();
+        for i in 0..count {
+            canvas.draw_rect(Rect::from_xywh(i as f32, 0.0, 1.0, 1.0), &paint);
+        }
+        recorder.finish_recording_as_picture(None).unwrap()
+    }
+
+    #[test]
+    fn playback_aborts() {
+        let picture = record_rects(10);
+        let mut surface = Surface::new_raster_n32_premul((100, 100)).unwrap();
+        let mut polled = 0;
+        picture.playback_with_abort(surface.canvas(), || {
+            polled += 1;
+            polled > 3
+        });
+        assert_eq!(polled, 4);
+    }
+
+    #[test]
+    fn playback_range_draws_a_subset() {
+        let picture = record_rects(10);
+        assert_eq!(picture.approximate_op_count_nested(true), 10);
+        let mut surface = Surface::new_raster_n32_premul((100, 100)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        assert!(picture.playback_range(surface.canvas(), 2..4));
+        let pixmap = surface.peek_pixels().unwrap();
+        let color = |x: i32| pixmap.get_color((x, 0));
+        assert_eq!(color(1), Color::WHITE);
+        assert_eq!(color(2), Color::BLACK);
+        assert_eq!(color(3), Color::BLACK);
+        assert_eq!(color(4), Color::WHITE);
+    }
+
+    #[test]
+    fn playback_range_is_clamped() {
+        let picture = record_rects(10);
+        let mut surface = Surface::new_raster_n32_premul((100, 100)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        assert!(picture.playback_range(surface.canvas(), 8..std::usize::MAX));
+        assert!(picture.playback_range(surface.canvas(), std::usize::MAX..std::usize::MAX));
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((7, 0)), Color::WHITE);
+        assert_eq!(pixmap.get_color((8, 0)), Color::BLACK);
+        assert_eq!(pixmap.get_color((9, 0)), Color::BLACK);
+    }
+}
@@ -28,8 +28,11 @@ impl Handle<SkPictureRecorder> {
         Self::from_native(unsafe { SkPictureRecorder::new() })
     }
 
-    // TODO: beginRecording with BBoxHierarchy
-
+    /// Begins recording into the returned canvas.
+    ///
+    /// If `bbh_factory` is given, the bounds of the recorded commands are indexed in a bounding
+    /// box hierarchy, which can be queried with `BBHFactory::bbox_hierarchy()` after the
+    /// recording is finished. The resulting picture uses it to skip commands outside of the clip.
     pub fn begin_recording(
         &mut self,
         bounds: impl AsRef<Rect>,
@@ -8,7 +8,7 @@ use skia_bindings::SkPixmap;
 use std::convert::TryInto;
 use std::ffi::c_void;
 use std::os::raw;
-use std::{ptr, slice};
+use std::{mem, ptr, slice};
 
 pub type Pixmap = Handle<SkPixmap>;
 
@@ -174,6 +174,69 @@ impl Handle<SkPixmap> {
     // TODO: writable_addr64
     // TODO: writable_addrF16
 
+    /// Returns the pixels as a slice of `P`, or `None` if there are no pixels or if `P` does not
+    /// match the color type.
+    ///
+    /// The slice includes the padding between the rows, use `rows()` to access the
+    /// pixels row by row.
+    pub fn pixels<P: Pixel>(&self) -> Option<&[P]> {
+        let len = self.pixels_len::<P>()?;
+        Some(unsafe { slice::from_raw_parts(self.addr() as *const P, len) })
+    }
+
+    /// Returns the pixels as a mutable slice of `P`, see `pixels()`.
+    ///
+    /// # Safety
+    /// A `Pixmap` does not own its pixels. The caller must make sure that they are writable and
+    /// that nothing else reads or writes them while the slice is alive. This is not the case for
+    /// pixmaps that are returned by `Image::peek_pixels()`, for example.
+    pub unsafe fn pixels_mut<P: Pixel>(&mut self) -> Option<&mut [P]> {
+        let len = self.pixels_len::<P>()?;
+        Some(slice::from_raw_parts_mut(
+            self.writable_addr() as *mut P,
+            len,
+        ))
+    }
+
+    /// Returns an iterator over the rows of pixels, each containing `width()` pixels, or `None`
+    /// under the same conditions as `pixels()`.
+    ///
+    /// The padding between the rows is skipped.
+    pub fn rows<P: Pixel>(&self) -> Option<impl Iterator<Item = &[P]>> {
+        let (width, stride) = self.row_layout::<P>();
+        let pixels = self.pixels::<P>()?;
+        Some(pixels.chunks(stride).map(move |row| &row[..width]))
+    }
+
+    /// Returns an iterator over the mutable rows of pixels, see `rows()`.
+    ///
+    /// # Safety
+    /// The same requirements as for `pixels_mut()` apply.
+    pub unsafe fn rows_mut<P: Pixel>(&mut self) -> Option<impl Iterator<Item = &mut [P]>> {
+        let (width, stride) = self.row_layout::<P>();
+        let pixels = self.pixels_mut::<P>()?;
+        Some(pixels.chunks_mut(stride).map(move |row| &mut row[..width]))
+    }
+
+    fn pixels_len<P: Pixel>(&self) -> Option<usize> {
+        let addr = unsafe { self.addr() };
+        if addr.is_null()
+            || !P::matches_color_type(self.color_type())
+            || (addr as usize) % mem::align_of::<P>() != 0
+            || self.row_bytes() % mem::size_of::<P>() != 0
+        {
+            return None;
+        }
+        Some(self.compute_byte_size() / mem::size_of::<P>())
+    }
+
+    // Returns the width and the distance between two rows in pixels.
+    fn row_layout<P: Pixel>(&self) -> (usize, usize) {
+        let width = self.width().try_into().unwrap();
+        let stride = self.row_bytes() / mem::size_of::<P>();
+        (width, stride.max(1))
+    }
+
     pub fn read_pixels<P>(
         &self,
         dst_info: &ImageInfo,
@@ -236,3 +299,84 @@ impl Handle<SkPixmap> {
         }
     }
 }
+
+/// Types that provide typed access to the pixels of a `Pixmap` or a `Bitmap`.
+///
+/// A `Pixel` describes how a pixel is stored, not how its channels are ordered or encoded.
+/// Except for `[f32; 4]` and `Color4f`, the types match all color types with the same number
+/// of bytes per pixel, `u32` for example matches `RGBA8888` as well as `BGRA8888` and
+/// `RGBA1010102`.
+///
+/// # Safety
+/// Implementations must be plain data types that are valid for every bit pattern of a pixel
+/// of the color types they match.
+pub unsafe trait Pixel: Sized {
+    fn matches_color_type(ct: ColorType) -> bool;
+}
+
+unsafe impl Pixel for u8 {
+    fn matches_color_type(ct: ColorType) -> bool {
+        ct.bytes_per_pixel() == 1
+    }
+}
+
+unsafe impl Pixel for u16 {
+    fn matches_color_type(ct: ColorType) -> bool {
+        ct.bytes_per_pixel() == 2
+    }
+}
+
+unsafe impl Pixel for [u8; 2] {
+    fn matches_color_type(ct: ColorType) -> bool {
+        ct.bytes_per_pixel() == 2
+    }
+}
+
+unsafe impl Pixel for u32 {
+    fn matches_color_type(ct: ColorType) -> bool {
+        ct.bytes_per_pixel() == 4
+    }
+}
+
+unsafe impl Pixel for [u8; 4] {
+    fn matches_color_type(ct: ColorType) -> bool {
+        ct.bytes_per_pixel() == 4
+    }
+}
+
+unsafe impl Pixel for u64 {
+    fn matches_color_type(ct: ColorType) -> bool {
+        ct.bytes_per_pixel() == 8
+    }
+}
+
+unsafe impl Pixel for [u16; 4] {
+    fn matches_color_type(ct: ColorType) -> bool {
+        ct.bytes_per_pixel() == 8
+    }
+}
+
+unsafe impl Pixel for [f32; 4] {
+    fn matches_color_type(ct: ColorType) -> bool {
+        ct == ColorType::RGBAF32
+    }
+}
+
+unsafe impl Pixel for Color4f {
+    fn matches_color_type(ct: ColorType) -> bool {
+        ct == ColorType::RGBAF32
+    }
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn color4f_matches_the_layout_of_rgbaf32_pixels() {
+    assert_eq!(
+        mem::size_of::<Color4f>(),
+        ColorType::RGBAF32.bytes_per_pixel()
+    );
+    assert_eq!(mem::align_of::<Color4f>(), mem::align_of::<f32>());
+
+    let color = Color4f::new(0.1, 0.2, 0.3, 0.4);
+    assert_eq!(color.as_array(), &[0.1, 0.2, 0.3, 0.4]);
+}
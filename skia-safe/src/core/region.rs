@@ -4,7 +4,9 @@ use skia_bindings as sb;
 use skia_bindings::{
     SkRegion, SkRegion_Cliperator, SkRegion_Iterator, SkRegion_RunHead, SkRegion_Spanerator,
 };
+use std::iter::FromIterator;
 use std::marker::PhantomData;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
 use std::{iter, mem, ptr};
 
 pub type Region = Handle<SkRegion>;
@@ -217,8 +219,116 @@ impl Handle<SkRegion> {
                 .readFromMemory(buf.as_ptr() as _, buf.len())
         }
     }
+
+    /// Returns the serialized region, see `write_to_memory()`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.write_to_memory(&mut buf);
+        buf
+    }
+
+    /// Reads a region serialized with `to_bytes()` or `write_to_memory()`. Returns `None` if
+    /// `buf` does not contain a valid region.
+    pub fn from_bytes(buf: &[u8]) -> Option<Region> {
+        let mut region = Region::new();
+        (region.read_from_memory(buf) != 0).if_true_some(region)
+    }
+
+    /// Returns the non-overlapping rectangles the region consists of, sorted by y and then x.
+    pub fn to_rects(&self) -> Vec<IRect> {
+        Iterator::new(self).collect()
+    }
+
+    /// Creates a region that is the union of the rectangles.
+    pub fn from_rects(rects: &[IRect]) -> Region {
+        let mut region = Region::new();
+        region.set_rects(rects);
+        region
+    }
+}
+
+impl FromIterator<IRect> for Region {
+    fn from_iter<I: IntoIterator<Item = IRect>>(iter: I) -> Self {
+        let rects: Vec<IRect> = iter.into_iter().collect();
+        Region::from_rects(&rects)
+    }
+}
+
+//
+// set operators
+//
+
+macro_rules! region_operators {
+    ($op_trait:ident, $op:ident, $assign_trait:ident, $assign:ident, $region_op:expr) => {
+        impl $assign_trait<&Region> for Region {
+            fn $assign(&mut self, rhs: &Region) {
+                self.op_region(rhs, $region_op);
+            }
+        }
+
+        impl $assign_trait<Region> for Region {
+            fn $assign(&mut self, rhs: Region) {
+                self.op_region(&rhs, $region_op);
+            }
+        }
+
+        impl $assign_trait<&IRect> for Region {
+            fn $assign(&mut self, rhs: &IRect) {
+                self.op_rect(rhs, $region_op);
+            }
+        }
+
+        impl $assign_trait<IRect> for Region {
+            fn $assign(&mut self, rhs: IRect) {
+                self.op_rect(rhs, $region_op);
+            }
+        }
+
+        impl $op_trait<&Region> for &Region {
+            type Output = Region;
+            fn $op(self, rhs: &Region) -> Region {
+                Region::combine(self, $region_op, rhs)
+            }
+        }
+
+        impl $op_trait<&IRect> for &Region {
+            type Output = Region;
+            fn $op(self, rhs: &IRect) -> Region {
+                Region::combine(self, $region_op, rhs)
+            }
+        }
+
+        impl $op_trait<IRect> for &Region {
+            type Output = Region;
+            fn $op(self, rhs: IRect) -> Region {
+                Region::combine(self, $region_op, &rhs)
+            }
+        }
+
+        impl<Rhs> $op_trait<Rhs> for Region
+        where
+            Region: $assign_trait<Rhs>,
+        {
+            type Output = Region;
+            fn $op(mut self, rhs: Rhs) -> Region {
+                self.$assign(rhs);
+                self
+            }
+        }
+    };
 }
 
+region_operators!(
+    BitAnd,
+    bitand,
+    BitAndAssign,
+    bitand_assign,
+    RegionOp::Intersect
+);
+region_operators!(BitOr, bitor, BitOrAssign, bitor_assign, RegionOp::Union);
+region_operators!(Sub, sub, SubAssign, sub_assign, RegionOp::Difference);
+region_operators!(BitXor, bitxor, BitXorAssign, bitxor_assign, RegionOp::XOR);
+
 //
 // combine overloads (static)
 //
@@ -513,3 +623,34 @@ fn can_compare() {
     let r2 = r1.clone();
     assert!(r1 == r2);
 }
+
+#[test]
+fn set_operators() {
+    let a = Region::from_rect(IRect::new(0, 0, 10, 10));
+    let b = IRect::new(5, 0, 15, 10);
+
+    assert_eq!(*(&a & &b).bounds(), IRect::new(5, 0, 10, 10));
+    assert_eq!(*(&a | b).bounds(), IRect::new(0, 0, 15, 10));
+    assert_eq!(*(&a - b).bounds(), IRect::new(0, 0, 5, 10));
+    assert_eq!(
+        (&a ^ &Region::from_rect(b)).to_rects(),
+        vec![IRect::new(0, 0, 5, 10), IRect::new(10, 0, 15, 10)]
+    );
+
+    let mut c = a.clone() | Region::from_rect(b);
+    c -= IRect::new(0, 0, 15, 5);
+    c &= &a;
+    assert!(c == Region::from_rect(IRect::new(0, 5, 10, 10)));
+}
+
+#[test]
+fn rects_and_bytes_round_trip() {
+    let rects = [IRect::new(0, 0, 10, 10), IRect::new(20, 20, 30, 40)];
+    let region: Region = rects.iter().cloned().collect();
+    assert_eq!(region.to_rects(), rects);
+    assert!(Region::from_rects(&region.to_rects()) == region);
+
+    let bytes = region.to_bytes();
+    assert!(Region::from_bytes(&bytes).unwrap() == region);
+    assert!(Region::from_bytes(&bytes[..3]).is_none());
+}
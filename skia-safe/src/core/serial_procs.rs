@@ -0,0 +1,214 @@
+use crate::prelude::*;
+use crate::{Data, Image, Picture, Typeface};
+use skia_bindings::{RustDeserialProcs, RustSerialProcs, SkData, SkImage, SkPicture, SkTypeface};
+use std::ffi::c_void;
+use std::slice;
+
+type SerialProc<'a, T> = Box<dyn FnMut(&T) -> Option<Data> + 'a>;
+type DeserialProc<'a, T> = Box<dyn FnMut(&[u8]) -> Option<T> + 'a>;
+
+/// Closures that replace the default serialization of pictures, images, and typefaces.
+///
+/// If a closure returns `None`, the object is serialized by Skia.
+#[derive(Default)]
+pub struct SerialProcs<'a> {
+    picture_proc: Option<SerialProc<'a, Picture>>,
+    image_proc: Option<SerialProc<'a, Image>>,
+    typeface_proc: Option<SerialProc<'a, Typeface>>,
+}
+
+impl<'a> SerialProcs<'a> {
+    /// Note that the closure is also called for the picture that gets serialized.
+    pub fn set_picture_proc(
+        &mut self,
+        proc: impl FnMut(&Picture) -> Option<Data> + 'a,
+    ) -> &mut Self {
+        self.picture_proc = Some(Box::new(proc));
+        self
+    }
+
+    pub fn set_image_proc(&mut self, proc: impl FnMut(&Image) -> Option<Data> + 'a) -> &mut Self {
+        self.image_proc = Some(Box::new(proc));
+        self
+    }
+
+    pub fn set_typeface_proc(
+        &mut self,
+        proc: impl FnMut(&Typeface) -> Option<Data> + 'a,
+    ) -> &mut Self {
+        self.typeface_proc = Some(Box::new(proc));
+        self
+    }
+
+    /// Returns the native procs, which point to `self`.
+    pub(crate) fn native_procs(&mut self) -> RustSerialProcs {
+        RustSerialProcs {
+            ctx: self as *mut Self as *mut c_void,
+            pictureProc: self.picture_proc.as_ref().map(|_| serial_picture as _),
+            imageProc: self.image_proc.as_ref().map(|_| serial_image as _),
+            typefaceProc: self.typeface_proc.as_ref().map(|_| serial_typeface as _),
+        }
+    }
+}
+
+unsafe extern "C" fn serial_picture(ctx: *mut c_void, picture: *mut SkPicture) -> *mut SkData {
+    let procs = &mut *(ctx as *mut SerialProcs);
+    let picture = Picture::from_unshared_ptr(picture).unwrap();
+    (procs.picture_proc.as_mut().unwrap())(&picture).into_ptr_or_null()
+}
+
+unsafe extern "C" fn serial_image(ctx: *mut c_void, image: *mut SkImage) -> *mut SkData {
+    let procs = &mut *(ctx as *mut SerialProcs);
+    let image = Image::from_unshared_ptr(image).unwrap();
+    (procs.image_proc.as_mut().unwrap())(&image).into_ptr_or_null()
+}
+
+unsafe extern "C" fn serial_typeface(ctx: *mut c_void, typeface: *mut SkTypeface) -> *mut SkData {
+    let procs = &mut *(ctx as *mut SerialProcs);
+    let typeface = Typeface::from_unshared_ptr(typeface).unwrap();
+    (procs.typeface_proc.as_mut().unwrap())(&typeface).into_ptr_or_null()
+}
+
+/// `SerialProcs` that are owned together with their native representation, for native objects
+/// that keep referring to them, like documents that serialize their pages when they are closed.
+pub(crate) struct OwnedSerialProcs {
+    // the native procs point to the boxed procs.
+    _procs: Box<SerialProcs<'static>>,
+    native: Box<RustSerialProcs>,
+}
+
+impl OwnedSerialProcs {
+    pub(crate) fn new(procs: SerialProcs<'static>) -> Self {
+        let mut procs = Box::new(procs);
+        let native = Box::new(procs.native_procs());
+        OwnedSerialProcs {
+            _procs: procs,
+            native,
+        }
+    }
+
+    pub(crate) fn native(&self) -> &RustSerialProcs {
+        &self.native
+    }
+}
+
+/// Closures that replace the default deserialization of pictures, images, and typefaces.
+///
+/// They receive the data that the matching `SerialProcs` closure returned. If a closure
+/// returns `None`, Skia falls back to its default deserialization where it supports one.
+#[derive(Default)]
+pub struct DeserialProcs<'a> {
+    picture_proc: Option<DeserialProc<'a, Picture>>,
+    image_proc: Option<DeserialProc<'a, Image>>,
+    typeface_proc: Option<DeserialProc<'a, Typeface>>,
+}
+
+impl<'a> DeserialProcs<'a> {
+    pub fn set_picture_proc(
+        &mut self,
+        proc: impl FnMut(&[u8]) -> Option<Picture> + 'a,
+    ) -> &mut Self {
+        self.picture_proc = Some(Box::new(proc));
+        self
+    }
+
+    pub fn set_image_proc(&mut self, proc: impl FnMut(&[u8]) -> Option<Image> + 'a) -> &mut Self {
+        self.image_proc = Some(Box::new(proc));
+        self
+    }
+
+    pub fn set_typeface_proc(
+        &mut self,
+        proc: impl FnMut(&[u8]) -> Option<Typeface> + 'a,
+    ) -> &mut Self {
+        self.typeface_proc = Some(Box::new(proc));
+        self
+    }
+
+    /// Returns the native procs, which point to `self`.
+    pub(crate) fn native_procs(&mut self) -> RustDeserialProcs {
+        RustDeserialProcs {
+            ctx: self as *mut Self as *mut c_void,
+            pictureProc: self.picture_proc.as_ref().map(|_| deserial_picture as _),
+            imageProc: self.image_proc.as_ref().map(|_| deserial_image as _),
+            typefaceProc: self.typeface_proc.as_ref().map(|_| deserial_typeface as _),
+        }
+    }
+}
+
+unsafe extern "C" fn deserial_picture(
+    ctx: *mut c_void,
+    data: *const c_void,
+    length: usize,
+) -> *mut SkPicture {
+    let procs = &mut *(ctx as *mut DeserialProcs);
+    let bytes = bytes_from_raw(data, length);
+    (procs.picture_proc.as_mut().unwrap())(bytes).into_ptr_or_null()
+}
+
+unsafe extern "C" fn deserial_image(
+    ctx: *mut c_void,
+    data: *const c_void,
+    length: usize,
+) -> *mut SkImage {
+    let procs = &mut *(ctx as *mut DeserialProcs);
+    let bytes = bytes_from_raw(data, length);
+    (procs.image_proc.as_mut().unwrap())(bytes).into_ptr_or_null()
+}
+
+unsafe extern "C" fn deserial_typeface(
+    ctx: *mut c_void,
+    data: *const c_void,
+    length: usize,
+) -> *mut SkTypeface {
+    let procs = &mut *(ctx as *mut DeserialProcs);
+    let bytes = bytes_from_raw(data, length);
+    (procs.typeface_proc.as_mut().unwrap())(bytes).into_ptr_or_null()
+}
+
+unsafe fn bytes_from_raw<'a>(data: *const c_void, length: usize) -> &'a [u8] {
+    if length == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data as *const u8, length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeserialProcs, SerialProcs};
+    use crate::{Color, Data, Picture, PictureRecorder, Rect, Surface};
+
+    #[test]
+    fn images_are_replaced_by_references() {
+        let mut surface = Surface::new_raster_n32_premul((8, 8)).unwrap();
+        surface.canvas().clear(Color::RED);
+        let image = surface.image_snapshot();
+
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 16.0, 16.0), None, None);
+        canvas.draw_image(&image, (0, 0), None);
+        canvas.draw_image(&image, (8, 8), None);
+        let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+        let mut serialized_images = 0;
+        let data = {
+            let mut procs = SerialProcs::default();
+            procs.set_image_proc(|_| {
+                serialized_images += 1;
+                Some(Data::new_copy(b"asset:1"))
+            });
+            picture.serialize_with_procs(&mut procs)
+        };
+        assert!(serialized_images >= 1);
+        assert!(data.len() < picture.serialize().len());
+
+        let mut procs = DeserialProcs::default();
+        procs.set_image_proc(|bytes| {
+            assert_eq!(bytes, b"asset:1");
+            Some(image.clone())
+        });
+        let deserialized = Picture::from_bytes_with_procs(&data, &mut procs).unwrap();
+        assert_eq!(deserialized.cull_rect(), picture.cull_rect());
+    }
+}
@@ -2,12 +2,13 @@
 use crate::gpu;
 use crate::prelude::*;
 use crate::{
-    Bitmap, Canvas, DeferredDisplayList, IPoint, IRect, ISize, Image, ImageInfo, Paint, Pixmap,
-    Size, SurfaceCharacterization, SurfaceProps,
+    Bitmap, Canvas, ColorSpace, DeferredDisplayList, FilterQuality, IPoint, IRect, ISize, Image,
+    ImageInfo, Paint, Pixmap, Size, SurfaceCharacterization, SurfaceProps, YUVColorSpace,
 };
 use skia_bindings as sb;
-use skia_bindings::{SkRefCntBase, SkSurface};
-use std::ptr;
+use skia_bindings::{RustAsyncReadResult, SkRefCntBase, SkSurface};
+use std::ffi::c_void;
+use std::{ptr, slice};
 
 pub use skia_bindings::SkSurface_ContentChangeMode as ContentChangeMode;
 #[test]
@@ -21,6 +22,66 @@ fn test_surface_backend_handle_access_naming() {
     let _ = BackendHandleAccess::FlushWrite;
 }
 
+/// Controls the gamma in which the rescaling of the asynchronous reads is performed.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum RescaleGamma {
+    Src,
+    Linear,
+}
+
+/// The pixels of an asynchronous read. The planes are only valid inside the callback.
+pub struct AsyncReadResult<'a> {
+    planes: Vec<(&'a [u8], usize)>,
+}
+
+impl<'a> AsyncReadResult<'a> {
+    /// The number of planes, 1 for a RGBA read and 3 for a YUV420 read.
+    pub fn count(&self) -> usize {
+        self.planes.len()
+    }
+
+    pub fn data(&self, i: usize) -> &'a [u8] {
+        self.planes[i].0
+    }
+
+    pub fn row_bytes(&self, i: usize) -> usize {
+        self.planes[i].1
+    }
+}
+
+type ReadPixelsCallback = Box<dyn FnOnce(Option<AsyncReadResult>)>;
+
+struct ReadPixelsContext {
+    callback: ReadPixelsCallback,
+    // the height and the minimum row bytes of each plane.
+    plane_sizes: [(usize, usize); 3],
+}
+
+unsafe extern "C" fn read_pixels_callback(ctx: *mut c_void, result: *const RustAsyncReadResult) {
+    let context = Box::from_raw(ctx as *mut ReadPixelsContext);
+    let result = result.as_ref().map(|result| {
+        let count: usize = result.count.try_into().unwrap();
+        let planes = (0..count)
+            .map(|i| {
+                let row_bytes = result.rowBytes[i];
+                let (height, min_row_bytes) = context.plane_sizes[i];
+                // the last row may not be padded to `row_bytes`.
+                let len = if height > 0 {
+                    row_bytes * (height - 1) + min_row_bytes
+                } else {
+                    0
+                };
+                (
+                    slice::from_raw_parts(result.data[i] as *const u8, len),
+                    row_bytes,
+                )
+            })
+            .collect();
+        AsyncReadResult { planes }
+    });
+    (context.callback)(result)
+}
+
 pub type Surface = RCHandle<SkSurface>;
 
 impl NativeRefCountedBase for SkSurface {
@@ -434,7 +495,10 @@ impl RCHandle<SkSurface> {
         if dst_row_bytes < dst_info.min_row_bytes() {
             return false;
         };
-        let height: usize = dst_info.height().try_into().unwrap();
+        let height: usize = match dst_info.height().try_into() {
+            Ok(height) => height,
+            Err(_) => return false,
+        };
         if dst_pixels.len() < dst_row_bytes * height {
             return false;
         };
@@ -457,9 +521,85 @@ impl RCHandle<SkSurface> {
         unsafe { self.native_mut().readPixels2(bitmap.native(), src.x, src.y) }
     }
 
-    // TODO: wrap AsyncReadResult (m79)
-    // TODO: wrap asyncRescaleAndReadPixels (m76/m79)
-    // TODO: wrap asyncRescaleAndReadPixelsYUV420 (m77/m79)
+    /// Reads `src_rect` rescaled to the dimensions of `info` and calls `callback` with the
+    /// result, or with `None` if the read failed.
+    ///
+    /// On raster surfaces, the callback is called before this function returns. On GPU surfaces,
+    /// it is called when the context is flushed or checks for completed asynchronous work.
+    pub fn async_rescale_and_read_pixels(
+        &mut self,
+        info: &ImageInfo,
+        src_rect: impl AsRef<IRect>,
+        rescale_gamma: RescaleGamma,
+        rescale_quality: FilterQuality,
+        callback: impl FnOnce(Option<AsyncReadResult>) + 'static,
+    ) {
+        let height: usize = match info.height().try_into() {
+            Ok(height) => height,
+            Err(_) => return callback(None),
+        };
+        let context = Box::new(ReadPixelsContext {
+            callback: Box::new(callback),
+            plane_sizes: [(height, info.min_row_bytes()), (0, 0), (0, 0)],
+        });
+        unsafe {
+            sb::C_SkSurface_asyncRescaleAndReadPixels(
+                self.native_mut(),
+                info.native(),
+                src_rect.as_ref().native(),
+                rescale_gamma == RescaleGamma::Linear,
+                rescale_quality,
+                Box::into_raw(context) as _,
+                Some(read_pixels_callback),
+            )
+        }
+    }
+
+    /// Reads `src_rect` rescaled to `dst_size` and converted to YUV420 and calls `callback`
+    /// with the Y, U, and V planes, or with `None` if the read failed.
+    ///
+    /// `dst_size` must have even dimensions. Skia supports YUV420 reads on GPU surfaces only, on
+    /// raster surfaces `callback` is called with `None`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn async_rescale_and_read_pixels_yuv420(
+        &mut self,
+        yuv_color_space: YUVColorSpace,
+        dst_color_space: impl Into<Option<ColorSpace>>,
+        src_rect: impl AsRef<IRect>,
+        dst_size: impl Into<ISize>,
+        rescale_gamma: RescaleGamma,
+        rescale_quality: FilterQuality,
+        callback: impl FnOnce(Option<AsyncReadResult>) + 'static,
+    ) {
+        let dst_size = dst_size.into();
+        let (width, height): (usize, usize) =
+            match (dst_size.width.try_into(), dst_size.height.try_into()) {
+                (Ok(width), Ok(height)) => (width, height),
+                _ => return callback(None),
+            };
+        let (uv_width, uv_height) = ((width + 1) / 2, (height + 1) / 2);
+        let context = Box::new(ReadPixelsContext {
+            callback: Box::new(callback),
+            plane_sizes: [
+                (height, width),
+                (uv_height, uv_width),
+                (uv_height, uv_width),
+            ],
+        });
+        unsafe {
+            sb::C_SkSurface_asyncRescaleAndReadPixelsYUV420(
+                self.native_mut(),
+                yuv_color_space,
+                dst_color_space.into().into_ptr_or_null(),
+                src_rect.as_ref().native(),
+                dst_size.native(),
+                rescale_gamma == RescaleGamma::Linear,
+                rescale_quality,
+                Box::into_raw(context) as _,
+                Some(read_pixels_callback),
+            )
+        }
+    }
 
     pub fn write_pixels_from_pixmap(&mut self, src: &Pixmap, dst: impl Into<IPoint>) {
         let dst = dst.into();
@@ -525,3 +665,63 @@ fn test_raster_direct() {
     let paint = Paint::default();
     surface.canvas().draw_circle((10, 10), 10.0, &paint);
 }
+
+#[test]
+fn test_async_rescale_and_read_pixels() {
+    use crate::{AlphaType, Color, ColorType};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let mut surface = Surface::new_raster_n32_premul((16, 8)).unwrap();
+    surface.canvas().clear(Color::WHITE);
+
+    let info = ImageInfo::new((8, 4), ColorType::RGBA8888, AlphaType::Premul, None);
+    let read = Rc::new(RefCell::new(None));
+    let read_in_callback = read.clone();
+    surface.async_rescale_and_read_pixels(
+        &info,
+        IRect::from_wh(16, 8),
+        RescaleGamma::Linear,
+        FilterQuality::Medium,
+        move |result| {
+            let result = result.unwrap();
+            assert_eq!(result.count(), 1);
+            let row_bytes = result.row_bytes(0);
+            assert!(row_bytes >= 8 * 4);
+            assert_eq!(result.data(0).len(), row_bytes * 3 + 8 * 4);
+            let pixels: Vec<u8> = (0..4)
+                .flat_map(|row| result.data(0)[row * row_bytes..][..8 * 4].to_vec())
+                .collect();
+            *read_in_callback.borrow_mut() = Some(pixels);
+        },
+    );
+
+    let pixels = read.borrow_mut().take().unwrap();
+    assert!(pixels.iter().all(|b| *b == 0xff));
+}
+
+#[test]
+fn test_async_rescale_and_read_pixels_yuv420_is_not_supported_on_raster_surfaces() {
+    use crate::Color;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let mut surface = Surface::new_raster_n32_premul((16, 8)).unwrap();
+    surface.canvas().clear(Color::RED);
+
+    let called = Rc::new(Cell::new(false));
+    let called_in_callback = called.clone();
+    surface.async_rescale_and_read_pixels_yuv420(
+        YUVColorSpace::JPEG,
+        None,
+        IRect::from_wh(16, 8),
+        (8, 4),
+        RescaleGamma::Src,
+        FilterQuality::Low,
+        move |result| {
+            assert!(result.is_none());
+            called_in_callback.set(true);
+        },
+    );
+    assert!(called.get());
+}
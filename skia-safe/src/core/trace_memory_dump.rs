@@ -1 +1,133 @@
-// TODO: wrap SkTraceMemoryDump
+use skia_bindings::{RustTraceMemoryDumpParam, SkTraceMemoryDump_LevelOfDetail};
+use std::borrow::Cow;
+use std::ffi::{c_void, CStr};
+use std::os::raw;
+
+pub use skia_bindings::SkTraceMemoryDump_LevelOfDetail as LevelOfDetail;
+#[test]
+fn test_level_of_detail_naming() {
+    let _ = LevelOfDetail::ObjectsBreakdowns;
+}
+
+/// Receives the memory statistics of Skia's caches, see `graphics::dump_memory_statistics()`
+/// and `gpu::Context::dump_memory_statistics()`.
+pub trait TraceMemoryDump {
+    /// Appends a new memory dump (or amends an existing one) with the value of a metric.
+    fn dump_numeric_value(&mut self, dump_name: &str, value_name: &str, units: &str, value: u64);
+
+    /// Sets the backing of a memory dump, for example the id of a GPU resource.
+    fn set_memory_backing(&mut self, dump_name: &str, backing_type: &str, backing_object_id: &str);
+
+    /// Returns the level of detail that is requested for the dump.
+    fn requested_details(&self) -> LevelOfDetail;
+}
+
+/// Calls `f` with the native param that forwards to `dump`.
+pub(crate) fn with_native_param<R>(
+    mut dump: &mut dyn TraceMemoryDump,
+    f: impl FnOnce(&RustTraceMemoryDumpParam) -> R,
+) -> R {
+    let param = RustTraceMemoryDumpParam {
+        ctx: &mut dump as *mut &mut dyn TraceMemoryDump as _,
+        dumpNumericValue: Some(dump_numeric_value),
+        setMemoryBacking: Some(set_memory_backing),
+        getRequestedDetails: Some(get_requested_details),
+    };
+    f(&param)
+}
+
+unsafe extern "C" fn dump_numeric_value(
+    ctx: *mut c_void,
+    dump_name: *const raw::c_char,
+    value_name: *const raw::c_char,
+    units: *const raw::c_char,
+    value: u64,
+) {
+    let dump = &mut *(ctx as *mut &mut dyn TraceMemoryDump);
+    dump.dump_numeric_value(
+        &to_str(dump_name),
+        &to_str(value_name),
+        &to_str(units),
+        value,
+    )
+}
+
+unsafe extern "C" fn set_memory_backing(
+    ctx: *mut c_void,
+    dump_name: *const raw::c_char,
+    backing_type: *const raw::c_char,
+    backing_object_id: *const raw::c_char,
+) {
+    let dump = &mut *(ctx as *mut &mut dyn TraceMemoryDump);
+    dump.set_memory_backing(
+        &to_str(dump_name),
+        &to_str(backing_type),
+        &to_str(backing_object_id),
+    )
+}
+
+unsafe extern "C" fn get_requested_details(ctx: *const c_void) -> SkTraceMemoryDump_LevelOfDetail {
+    let dump = &*(ctx as *const &mut dyn TraceMemoryDump);
+    dump.requested_details()
+}
+
+unsafe fn to_str<'a>(str: *const raw::c_char) -> Cow<'a, str> {
+    if str.is_null() {
+        return Cow::Borrowed("");
+    }
+    CStr::from_ptr(str).to_string_lossy()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LevelOfDetail, TraceMemoryDump};
+    use crate::graphics;
+
+    #[derive(Default)]
+    struct Values(Vec<(String, String, u64)>);
+
+    impl TraceMemoryDump for Values {
+        fn dump_numeric_value(&mut self, dump_name: &str, value_name: &str, _: &str, value: u64) {
+            self.0.push((dump_name.into(), value_name.into(), value))
+        }
+
+        fn set_memory_backing(&mut self, _: &str, _: &str, _: &str) {}
+
+        fn requested_details(&self) -> LevelOfDetail {
+            LevelOfDetail::Light
+        }
+    }
+
+    #[test]
+    fn dump_graphics_memory_statistics() {
+        let mut values = Values::default();
+        graphics::dump_memory_statistics(&mut values);
+        assert!(values.0.iter().any(
+            |(dump_name, value_name, _)| dump_name.starts_with("skia/") && value_name == "size"
+        ));
+    }
+
+    #[cfg(feature = "gpu")]
+    #[test]
+    fn dump_gpu_memory_statistics() {
+        use crate::gpu::{Context, SurfaceOrigin};
+        use crate::{Budgeted, ImageInfo, Surface};
+
+        let mut context = Context::new_mock(None).unwrap();
+        let info = ImageInfo::new_n32_premul((16, 16), None);
+        let _surface = Surface::new_render_target(
+            &mut context,
+            Budgeted::YES,
+            &info,
+            None,
+            SurfaceOrigin::TopLeft,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let mut values = Values::default();
+        context.dump_memory_statistics(&mut values);
+        assert!(!values.0.is_empty());
+    }
+}
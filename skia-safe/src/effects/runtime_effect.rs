@@ -1,6 +1,6 @@
 use crate::interop::AsStr;
 use crate::prelude::*;
-use crate::{interop, ColorFilter, Data, Matrix, Shader};
+use crate::{interop, scalar, ColorFilter, Data, Matrix, Matrix44, Shader};
 use skia_bindings as sb;
 use skia_bindings::{SkRefCntBase, SkRuntimeEffect, SkRuntimeEffect_Variable};
 use std::slice;
@@ -72,6 +72,21 @@ pub mod variable {
             const ARRAY = sb::SkRuntimeEffect_Variable_Flags_kArray_Flag as _;
         }
     }
+
+    /// The SkSL name of the type.
+    pub fn type_name(ty: Type) -> &'static str {
+        match ty {
+            Type::Bool => "bool",
+            Type::Int => "int",
+            Type::Float => "float",
+            Type::Float2 => "float2",
+            Type::Float3 => "float3",
+            Type::Float4 => "float4",
+            Type::Float2x2 => "float2x2",
+            Type::Float3x3 => "float3x3",
+            Type::Float4x4 => "float4x4",
+        }
+    }
 }
 
 pub type RuntimeEffect = RCHandle<SkRuntimeEffect>;
@@ -146,6 +161,21 @@ impl RCHandle<SkRuntimeEffect> {
         }
     }
 
+    pub fn find_input(&self, name: impl AsRef<str>) -> Option<&Variable> {
+        let name = name.as_ref();
+        self.inputs().iter().find(|v| v.name() == name)
+    }
+
+    pub fn find_child(&self, name: impl AsRef<str>) -> Option<usize> {
+        let name = name.as_ref();
+        self.children().position(|child| child == name)
+    }
+
+    /// Creates a builder that assembles the inputs and children of this effect.
+    pub fn builder(&self) -> Builder {
+        Builder::new(self)
+    }
+
     pub fn children(&self) -> impl Iterator<Item = &str> {
         unsafe {
             let mut count: usize = 0;
@@ -158,3 +188,289 @@ impl RCHandle<SkRuntimeEffect> {
     // TODO: wrap toPipelineStage()
     // TODO: wrap toByteCode()
 }
+
+/// Assembles the input block and the child shaders of a `RuntimeEffect` by name.
+///
+/// Every value is checked against the name, type, and size of the effect's `inputs()` and
+/// `children()`, so that no input block needs to be laid out by hand.
+pub struct Builder {
+    effect: RuntimeEffect,
+    inputs: Vec<u8>,
+    children: Vec<Option<Shader>>,
+}
+
+impl Builder {
+    pub fn new(effect: &RuntimeEffect) -> Self {
+        Builder {
+            effect: effect.clone(),
+            inputs: vec![0; effect.input_size()],
+            children: vec![None; effect.children().count()],
+        }
+    }
+
+    pub fn effect(&self) -> &RuntimeEffect {
+        &self.effect
+    }
+
+    pub fn set_bool(&mut self, name: impl AsRef<str>, value: bool) -> Result<&mut Self, String> {
+        self.set_input(name, variable::Type::Bool, None, &[value as u8])
+    }
+
+    pub fn set_int(&mut self, name: impl AsRef<str>, value: i32) -> Result<&mut Self, String> {
+        self.set_input(name, variable::Type::Int, None, &value.to_ne_bytes())
+    }
+
+    pub fn set_int_array(
+        &mut self,
+        name: impl AsRef<str>,
+        values: &[i32],
+    ) -> Result<&mut Self, String> {
+        let bytes: Vec<u8> = values
+            .iter()
+            .flat_map(|v| v.to_ne_bytes().to_vec())
+            .collect();
+        self.set_input(name, variable::Type::Int, Some(values.len()), &bytes)
+    }
+
+    pub fn set_float(&mut self, name: impl AsRef<str>, value: scalar) -> Result<&mut Self, String> {
+        self.set_floats(name, variable::Type::Float, None, &[value])
+    }
+
+    pub fn set_float2(
+        &mut self,
+        name: impl AsRef<str>,
+        value: [scalar; 2],
+    ) -> Result<&mut Self, String> {
+        self.set_floats(name, variable::Type::Float2, None, &value)
+    }
+
+    pub fn set_float3(
+        &mut self,
+        name: impl AsRef<str>,
+        value: [scalar; 3],
+    ) -> Result<&mut Self, String> {
+        self.set_floats(name, variable::Type::Float3, None, &value)
+    }
+
+    pub fn set_float4(
+        &mut self,
+        name: impl AsRef<str>,
+        value: [scalar; 4],
+    ) -> Result<&mut Self, String> {
+        self.set_floats(name, variable::Type::Float4, None, &value)
+    }
+
+    /// Sets a `float2x2` input from its elements in column-major order.
+    pub fn set_float2x2(
+        &mut self,
+        name: impl AsRef<str>,
+        value: [scalar; 4],
+    ) -> Result<&mut Self, String> {
+        self.set_floats(name, variable::Type::Float2x2, None, &value)
+    }
+
+    /// Sets a `float3x3` input from its elements in column-major order.
+    pub fn set_float3x3(
+        &mut self,
+        name: impl AsRef<str>,
+        value: [scalar; 9],
+    ) -> Result<&mut Self, String> {
+        self.set_floats(name, variable::Type::Float3x3, None, &value)
+    }
+
+    /// Sets a `float4x4` input from its elements in column-major order.
+    pub fn set_float4x4(
+        &mut self,
+        name: impl AsRef<str>,
+        value: [scalar; 16],
+    ) -> Result<&mut Self, String> {
+        self.set_floats(name, variable::Type::Float4x4, None, &value)
+    }
+
+    /// Sets a `float3x3` input to a `Matrix`.
+    pub fn set_matrix(&mut self, name: impl AsRef<str>, m: &Matrix) -> Result<&mut Self, String> {
+        self.set_float3x3(name, [m[0], m[3], m[6], m[1], m[4], m[7], m[2], m[5], m[8]])
+    }
+
+    /// Sets a `float4x4` input to a `Matrix44`.
+    pub fn set_matrix44(
+        &mut self,
+        name: impl AsRef<str>,
+        m: &Matrix44,
+    ) -> Result<&mut Self, String> {
+        let mut floats = [0.0; 16];
+        m.as_col_major(&mut floats);
+        self.set_float4x4(name, floats)
+    }
+
+    /// Sets an array input of `ty`. `values` contains the components of all elements, matrix
+    /// elements in column-major order.
+    pub fn set_float_array(
+        &mut self,
+        name: impl AsRef<str>,
+        ty: variable::Type,
+        values: &[scalar],
+    ) -> Result<&mut Self, String> {
+        let components = float_components(ty)
+            .ok_or_else(|| format!("'{}' is not a float type", variable::type_name(ty)))?;
+        if values.len() % components != 0 {
+            return Err(format!(
+                "the number of values ({}) is not a multiple of the {} components of a '{}'",
+                values.len(),
+                components,
+                variable::type_name(ty)
+            ));
+        }
+        self.set_floats(name, ty, Some(values.len() / components), values)
+    }
+
+    /// Binds the child shader with the given name.
+    pub fn set_child(
+        &mut self,
+        name: impl AsRef<str>,
+        shader: impl Into<Shader>,
+    ) -> Result<&mut Self, String> {
+        let name = name.as_ref();
+        let index = self
+            .effect
+            .find_child(name)
+            .ok_or_else(|| format!("runtime effect has no child named '{}'", name))?;
+        self.children[index] = Some(shader.into());
+        Ok(self)
+    }
+
+    /// Returns a copy of the input block.
+    pub fn inputs(&self) -> Data {
+        Data::new_copy(&self.inputs)
+    }
+
+    /// Creates the shader, returns `None` if not all children are bound.
+    pub fn make_shader<'a>(
+        &self,
+        local_matrix: impl Into<Option<&'a Matrix>>,
+        is_opaque: bool,
+    ) -> Option<Shader> {
+        let children: Option<Vec<Shader>> = self.children.iter().cloned().collect();
+        let inputs = self.inputs();
+        // SkRuntimeEffect::makeShader() is not const.
+        self.effect
+            .clone()
+            .make_shader(inputs, children?, local_matrix, is_opaque)
+    }
+
+    pub fn make_color_filter(&self) -> Option<ColorFilter> {
+        let inputs = self.inputs();
+        self.effect.clone().make_color_filter(inputs)
+    }
+
+    fn set_floats(
+        &mut self,
+        name: impl AsRef<str>,
+        ty: variable::Type,
+        count: Option<usize>,
+        values: &[scalar],
+    ) -> Result<&mut Self, String> {
+        let bytes: Vec<u8> = values
+            .iter()
+            .flat_map(|v| v.to_ne_bytes().to_vec())
+            .collect();
+        self.set_input(name, ty, count, &bytes)
+    }
+
+    fn set_input(
+        &mut self,
+        name: impl AsRef<str>,
+        ty: variable::Type,
+        count: Option<usize>,
+        bytes: &[u8],
+    ) -> Result<&mut Self, String> {
+        let name = name.as_ref();
+        let input = self
+            .effect
+            .find_input(name)
+            .ok_or_else(|| format!("runtime effect has no input named '{}'", name))?;
+
+        if input.ty() != ty {
+            return Err(format!(
+                "input '{}' is of type '{}', not '{}'",
+                name,
+                variable::type_name(input.ty()),
+                variable::type_name(ty)
+            ));
+        }
+        match count {
+            Some(count) if !input.is_array() || input.count() as usize != count => {
+                return Err(format!(
+                    "input '{}' is not an array of {} elements",
+                    name, count
+                ));
+            }
+            None if input.is_array() => {
+                return Err(format!("input '{}' is an array", name));
+            }
+            _ => {}
+        }
+
+        let offset = input.offset();
+        let size = input.size_in_bytes();
+        if size != bytes.len() {
+            return Err(format!(
+                "input '{}' has a size of {} bytes, not {}",
+                name,
+                size,
+                bytes.len()
+            ));
+        }
+        self.inputs[offset..offset + size].copy_from_slice(bytes);
+        Ok(self)
+    }
+}
+
+fn float_components(ty: variable::Type) -> Option<usize> {
+    use variable::Type;
+    let components = match ty {
+        Type::Bool | Type::Int => return None,
+        Type::Float => 1,
+        Type::Float2 => 2,
+        Type::Float3 => 3,
+        Type::Float4 | Type::Float2x2 => 4,
+        Type::Float3x3 => 9,
+        Type::Float4x4 => 16,
+    };
+    Some(components)
+}
+
+#[test]
+fn test_builder_checks_inputs() {
+    let effect = new(r"
+        uniform float2 offset;
+        uniform float weights[3];
+        in shader input;
+        void main(float x, float y, inout half4 color) {
+            color = sample(input, float2(x, y) + offset) * half(weights[0]);
+        }
+    ")
+    .unwrap();
+
+    let mut builder = effect.builder();
+    assert!(builder.set_float2("offset", [1.0, 2.0]).is_ok());
+    assert!(builder
+        .set_input("offset", variable::Type::Float2, None, &[0; 4])
+        .is_err());
+    assert!(builder.set_float("offset", 1.0).is_err());
+    assert!(builder.set_float("unknown", 1.0).is_err());
+    assert!(builder
+        .set_float_array("weights", variable::Type::Float, &[1.0, 2.0])
+        .is_err());
+    assert!(builder
+        .set_float_array("weights", variable::Type::Float, &[1.0, 2.0, 3.0])
+        .is_ok());
+    assert!(builder.make_shader(None, false).is_none());
+    assert!(builder.set_child("unknown", Shader::empty()).is_err());
+    builder.set_child("input", Shader::empty()).unwrap();
+    assert!(builder.make_shader(None, false).is_some());
+
+    let offset = effect.find_input("offset").unwrap().offset();
+    let inputs = builder.inputs();
+    assert_eq!(&inputs[offset..offset + 4], &1.0f32.to_ne_bytes());
+}
@@ -7,6 +7,8 @@ pub use self::backend_surface::*;
 mod context;
 pub use self::context::*;
 
+pub mod mock;
+
 mod types;
 pub use self::types::*;
 
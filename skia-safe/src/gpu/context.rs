@@ -2,9 +2,9 @@
 use super::gl;
 #[cfg(feature = "vulkan")]
 use super::vk;
-use crate::gpu::{BackendFormat, MipMapped, Renderable};
+use crate::gpu::{mock, BackendFormat, MipMapped, Renderable};
 use crate::prelude::*;
-use crate::{image, ColorType, Data, Image};
+use crate::{image, trace_memory_dump, ColorType, Data, Image, TraceMemoryDump};
 use skia_bindings as sb;
 use skia_bindings::{GrContext, SkRefCntBase};
 
@@ -57,6 +57,20 @@ impl RCHandle<GrContext> {
         Context::from_ptr(sb::C_GrContext_MakeMetal(device, queue))
     }
 
+    /// Creates a context for the mock backend, which does not need a GPU. Uses the default
+    /// options if `options` is `None`.
+    pub fn new_mock<'a>(options: impl Into<Option<&'a mock::Options>>) -> Option<Context> {
+        let options = options.into().map(|options| options.to_native());
+        Context::from_ptr(unsafe {
+            sb::C_GrContext_MakeMock(
+                options
+                    .as_ref()
+                    .map(|o| o as *const _)
+                    .unwrap_or(std::ptr::null()),
+            )
+        })
+    }
+
     // TODO: threadSafeProxy()
 
     pub fn reset(&mut self, backend_state: Option<u32>) -> &mut Self {
@@ -267,6 +281,12 @@ impl RCHandle<GrContext> {
 
     // TODO: wrap deleteBackendTexture(),
 
+    pub fn dump_memory_statistics(&self, dump: &mut dyn TraceMemoryDump) {
+        trace_memory_dump::with_native_param(dump, |param| unsafe {
+            sb::C_GrContext_dumpMemoryStatistics(self.native(), param)
+        })
+    }
+
     pub fn precompile_shader(&mut self, key: &Data, data: &Data) -> bool {
         unsafe {
             self.native_mut()
@@ -0,0 +1,104 @@
+use crate::prelude::*;
+use skia_bindings as sb;
+use skia_bindings::GrMockOptions;
+
+/// Options for the mock backend. A mock context does not render anything, but otherwise behaves
+/// like a context of a real GPU backend with the capabilities described here.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Options {
+    pub mip_map_support: bool,
+    pub instance_attrib_support: bool,
+    pub half_float_vertex_attribute_support: bool,
+    pub map_buffer_flags: u32,
+    pub max_texture_size: i32,
+    pub max_render_target_size: i32,
+    pub max_vertex_attributes: i32,
+    pub geometry_shader_support: bool,
+    pub integer_support: bool,
+    pub flat_interpolation_support: bool,
+    pub max_vertex_samplers: i32,
+    pub max_fragment_samplers: i32,
+    pub shader_derivative_support: bool,
+    pub dual_source_blending_support: bool,
+    pub fail_texture_allocations: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        let native = construct(|options| unsafe { sb::C_GrMockOptions_Construct(options) });
+        Options {
+            mip_map_support: native.fMipMapSupport,
+            instance_attrib_support: native.fInstanceAttribSupport,
+            half_float_vertex_attribute_support: native.fHalfFloatVertexAttributeSupport,
+            map_buffer_flags: native.fMapBufferFlags,
+            max_texture_size: native.fMaxTextureSize,
+            max_render_target_size: native.fMaxRenderTargetSize,
+            max_vertex_attributes: native.fMaxVertexAttributes,
+            geometry_shader_support: native.fGeometryShaderSupport,
+            integer_support: native.fIntegerSupport,
+            flat_interpolation_support: native.fFlatInterpolationSupport,
+            max_vertex_samplers: native.fMaxVertexSamplers,
+            max_fragment_samplers: native.fMaxFragmentSamplers,
+            shader_derivative_support: native.fShaderDerivativeSupport,
+            dual_source_blending_support: native.fDualSourceBlendingSupport,
+            fail_texture_allocations: native.fFailTextureAllocations,
+        }
+    }
+}
+
+impl Options {
+    /// Returns the native options. The per color type and compression type options are kept at
+    /// their defaults.
+    pub(crate) fn to_native(&self) -> GrMockOptions {
+        let mut native = construct(|options| unsafe { sb::C_GrMockOptions_Construct(options) });
+        native.fMipMapSupport = self.mip_map_support;
+        native.fInstanceAttribSupport = self.instance_attrib_support;
+        native.fHalfFloatVertexAttributeSupport = self.half_float_vertex_attribute_support;
+        native.fMapBufferFlags = self.map_buffer_flags;
+        native.fMaxTextureSize = self.max_texture_size;
+        native.fMaxRenderTargetSize = self.max_render_target_size;
+        native.fMaxVertexAttributes = self.max_vertex_attributes;
+        native.fGeometryShaderSupport = self.geometry_shader_support;
+        native.fIntegerSupport = self.integer_support;
+        native.fFlatInterpolationSupport = self.flat_interpolation_support;
+        native.fMaxVertexSamplers = self.max_vertex_samplers;
+        native.fMaxFragmentSamplers = self.max_fragment_samplers;
+        native.fShaderDerivativeSupport = self.shader_derivative_support;
+        native.fDualSourceBlendingSupport = self.dual_source_blending_support;
+        native.fFailTextureAllocations = self.fail_texture_allocations;
+        native
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::gpu::{Context, SurfaceOrigin};
+    use crate::{AlphaType, Budgeted, Color, ColorType, ImageInfo, Surface};
+
+    #[test]
+    fn create_mock_context() {
+        let context = Context::new_mock(None);
+        assert!(context.is_some());
+        assert!(!context.unwrap().abandoned());
+    }
+
+    #[test]
+    fn render_to_mock_surface() {
+        let mut context = Context::new_mock(&Options::default()).unwrap();
+        let info = ImageInfo::new((64, 64), ColorType::RGBA8888, AlphaType::Premul, None);
+        let mut surface = Surface::new_render_target(
+            &mut context,
+            Budgeted::YES,
+            &info,
+            None,
+            SurfaceOrigin::TopLeft,
+            None,
+            None,
+        )
+        .unwrap();
+        surface.canvas().clear(Color::RED);
+        surface.flush();
+        assert!(context.resource_cache_usage().resource_count > 0);
+    }
+}
@@ -65,6 +65,26 @@ pub trait RunIterator {
     fn at_end(&self) -> bool;
 }
 
+/// A Rust implementation of a font run iterator, see `FontRunIterator::from_impl()`.
+pub trait FontRunIteratorImpl: RunIterator {
+    fn current_font(&self) -> &Font;
+}
+
+/// A Rust implementation of a bidi run iterator, see `BiDiRunIterator::from_impl()`.
+pub trait BiDiRunIteratorImpl: RunIterator {
+    fn current_level(&self) -> u8;
+}
+
+/// A Rust implementation of a script run iterator, see `ScriptRunIterator::from_impl()`.
+pub trait ScriptRunIteratorImpl: RunIterator {
+    fn current_script(&self) -> FourByteTag;
+}
+
+/// A Rust implementation of a language run iterator, see `LanguageRunIterator::from_impl()`.
+pub trait LanguageRunIteratorImpl: RunIterator {
+    fn current_language(&self) -> &CStr;
+}
+
 impl<T> RunIterator for RefHandle<T>
 where
     T: NativeDrop,
@@ -94,6 +114,15 @@ impl NativeDrop for SkShaper_FontRunIterator {
 }
 
 impl RefHandle<SkShaper_FontRunIterator> {
+    /// Creates a native font run iterator that forwards to a Rust implementation.
+    pub fn from_impl<T: FontRunIteratorImpl + 'static>(iterator: T) -> Self {
+        let param = rust_run_iterator::new_param(iterator);
+        Self::from_ptr(unsafe {
+            sb::C_RustFontRunIterator_new(&param, Some(rust_run_iterator::current_font::<T>))
+        })
+        .unwrap()
+    }
+
     pub fn current_font(&self) -> &Font {
         Font::from_native_ref(unsafe {
             &*sb::C_SkShaper_FontRunIterator_currentFont(self.native())
@@ -142,6 +171,15 @@ impl NativeDrop for SkShaper_BiDiRunIterator {
 }
 
 impl RefHandle<SkShaper_BiDiRunIterator> {
+    /// Creates a native bidi run iterator that forwards to a Rust implementation.
+    pub fn from_impl<T: BiDiRunIteratorImpl + 'static>(iterator: T) -> Self {
+        let param = rust_run_iterator::new_param(iterator);
+        Self::from_ptr(unsafe {
+            sb::C_RustBiDiRunIterator_new(&param, Some(rust_run_iterator::current_level::<T>))
+        })
+        .unwrap()
+    }
+
     pub fn current_level(&self) -> u8 {
         unsafe { sb::C_SkShaper_BiDiRunIterator_currentLevel(self.native()) }
     }
@@ -183,6 +221,15 @@ impl NativeDrop for SkShaper_ScriptRunIterator {
 }
 
 impl RefHandle<SkShaper_ScriptRunIterator> {
+    /// Creates a native script run iterator that forwards to a Rust implementation.
+    pub fn from_impl<T: ScriptRunIteratorImpl + 'static>(iterator: T) -> Self {
+        let param = rust_run_iterator::new_param(iterator);
+        Self::from_ptr(unsafe {
+            sb::C_RustScriptRunIterator_new(&param, Some(rust_run_iterator::current_script::<T>))
+        })
+        .unwrap()
+    }
+
     pub fn current_script(&self) -> FourByteTag {
         FourByteTag::from_native(unsafe {
             sb::C_SkShaper_ScriptRunIterator_currentScript(self.native())
@@ -232,6 +279,18 @@ impl NativeDrop for SkShaper_LanguageRunIterator {
 }
 
 impl RefHandle<SkShaper_LanguageRunIterator> {
+    /// Creates a native language run iterator that forwards to a Rust implementation.
+    pub fn from_impl<T: LanguageRunIteratorImpl + 'static>(iterator: T) -> Self {
+        let param = rust_run_iterator::new_param(iterator);
+        Self::from_ptr(unsafe {
+            sb::C_RustLanguageRunIterator_new(
+                &param,
+                Some(rust_run_iterator::current_language::<T>),
+            )
+        })
+        .unwrap()
+    }
+
     pub fn current_language(&self) -> &CStr {
         unsafe {
             CStr::from_ptr(sb::C_SkShaper_LanguageRunIterator_currentLanguage(
@@ -500,6 +559,71 @@ mod rust_run_handler {
     }
 }
 
+mod rust_run_iterator {
+    use super::{
+        BiDiRunIteratorImpl, FontRunIteratorImpl, LanguageRunIteratorImpl, RunIterator,
+        ScriptRunIteratorImpl,
+    };
+    use crate::prelude::*;
+    use skia_bindings::{RustRunIteratorParam, SkFont, SkFourByteTag};
+    use std::ffi::c_void;
+    use std::os::raw;
+
+    /// Moves the iterator to the heap. The native iterator takes ownership of it and drops it
+    /// when it gets deleted.
+    pub fn new_param<T: RunIterator>(iterator: T) -> RustRunIteratorParam {
+        RustRunIteratorParam {
+            ctx: Box::into_raw(Box::new(iterator)) as _,
+            consume: Some(consume::<T>),
+            endOfCurrentRun: Some(end_of_current_run::<T>),
+            atEnd: Some(at_end::<T>),
+            drop: Some(drop::<T>),
+        }
+    }
+
+    pub unsafe extern "C" fn current_font<T: FontRunIteratorImpl>(
+        ctx: *const c_void,
+    ) -> *const SkFont {
+        iterator::<T>(ctx).current_font().native()
+    }
+
+    pub unsafe extern "C" fn current_level<T: BiDiRunIteratorImpl>(ctx: *const c_void) -> u8 {
+        iterator::<T>(ctx).current_level()
+    }
+
+    pub unsafe extern "C" fn current_script<T: ScriptRunIteratorImpl>(
+        ctx: *const c_void,
+    ) -> SkFourByteTag {
+        iterator::<T>(ctx).current_script().into_native()
+    }
+
+    pub unsafe extern "C" fn current_language<T: LanguageRunIteratorImpl>(
+        ctx: *const c_void,
+    ) -> *const raw::c_char {
+        iterator::<T>(ctx).current_language().as_ptr()
+    }
+
+    unsafe extern "C" fn consume<T: RunIterator>(ctx: *mut c_void) {
+        (*(ctx as *mut T)).consume()
+    }
+
+    unsafe extern "C" fn end_of_current_run<T: RunIterator>(ctx: *const c_void) -> usize {
+        iterator::<T>(ctx).end_of_current_run()
+    }
+
+    unsafe extern "C" fn at_end<T: RunIterator>(ctx: *const c_void) -> bool {
+        iterator::<T>(ctx).at_end()
+    }
+
+    unsafe extern "C" fn drop<T>(ctx: *mut c_void) {
+        std::mem::drop(Box::from_raw(ctx as *mut T))
+    }
+
+    unsafe fn iterator<'a, T>(ctx: *const c_void) -> &'a T {
+        &*(ctx as *const T)
+    }
+}
+
 #[repr(transparent)]
 pub struct TextBlobBuilderRunHandler<'text>(SkTextBlobBuilderRunHandler, PhantomData<&'text str>);
 
@@ -641,4 +765,102 @@ mod tests {
             &mut DebugRunHandler::default(),
         );
     }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_rust_run_iterators() {
+        use crate::shaper::{
+            BiDiRunIterator, BiDiRunIteratorImpl, FontRunIterator, FontRunIteratorImpl,
+            LanguageRunIterator, LanguageRunIteratorImpl, RunIterator, ScriptRunIterator,
+            ScriptRunIteratorImpl,
+        };
+        use crate::FourByteTag;
+        use std::cell::Cell;
+        use std::ffi::CStr;
+        use std::rc::Rc;
+
+        // A single run that covers the whole text.
+        struct SingleRun {
+            end: usize,
+            consumed: bool,
+            font: Font,
+            dropped: Rc<Cell<usize>>,
+        }
+
+        impl Drop for SingleRun {
+            fn drop(&mut self) {
+                self.dropped.set(self.dropped.get() + 1);
+            }
+        }
+
+        impl RunIterator for SingleRun {
+            fn consume(&mut self) {
+                assert!(!self.consumed);
+                self.consumed = true;
+            }
+            fn end_of_current_run(&self) -> usize {
+                self.end
+            }
+            fn at_end(&self) -> bool {
+                self.consumed
+            }
+        }
+
+        impl FontRunIteratorImpl for SingleRun {
+            fn current_font(&self) -> &Font {
+                &self.font
+            }
+        }
+
+        impl BiDiRunIteratorImpl for SingleRun {
+            fn current_level(&self) -> u8 {
+                0
+            }
+        }
+
+        impl ScriptRunIteratorImpl for SingleRun {
+            fn current_script(&self) -> FourByteTag {
+                FourByteTag::new(u32::from_be_bytes(*b"Latn"))
+            }
+        }
+
+        impl LanguageRunIteratorImpl for SingleRun {
+            fn current_language(&self) -> &CStr {
+                CStr::from_bytes_with_nul(b"en\0").unwrap()
+            }
+        }
+
+        let text = "Hello";
+        let dropped = Rc::new(Cell::new(0));
+        let run = || SingleRun {
+            end: text.len(),
+            consumed: false,
+            font: Font::default(),
+            dropped: dropped.clone(),
+        };
+
+        {
+            let mut font_runs = FontRunIterator::from_impl(run());
+            let mut bidi_runs = BiDiRunIterator::from_impl(run());
+            let mut script_runs = ScriptRunIterator::from_impl(run());
+            let mut language_runs = LanguageRunIterator::from_impl(run());
+            assert!(!font_runs.at_end());
+            assert_eq!(language_runs.current_language().to_str().unwrap(), "en");
+
+            let mut handler = DebugRunHandler::default();
+            Shaper::new(None).shape_with_iterators(
+                text,
+                &mut font_runs,
+                &mut bidi_runs,
+                &mut script_runs,
+                &mut language_runs,
+                10000.0,
+                &mut handler,
+            );
+            assert!(font_runs.at_end());
+            assert!(!handler.glyphs.is_empty());
+        }
+
+        assert_eq!(dropped.get(), 4);
+    }
 }
@@ -1,15 +1,58 @@
+/// Implements `Deref`, `DerefMut`, and `AsMut` to `Canvas` for a canvas wrapper that keeps its
+/// native canvas in an `OwnedCanvas` field named `canvas`.
+macro_rules! canvas_wrapper {
+    ($wrapper:ident) => {
+        impl std::ops::Deref for $wrapper<'_> {
+            type Target = crate::Canvas;
+
+            fn deref(&self) -> &Self::Target {
+                &self.canvas
+            }
+        }
+
+        impl std::ops::DerefMut for $wrapper<'_> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.canvas
+            }
+        }
+
+        impl AsMut<crate::Canvas> for $wrapper<'_> {
+            fn as_mut(&mut self) -> &mut crate::Canvas {
+                &mut self.canvas
+            }
+        }
+    };
+}
+
 mod _3d;
 pub use _3d::*;
 
 mod camera;
 pub use camera::*;
 
+mod draw_command_canvas;
+pub use draw_command_canvas::*;
+
+#[cfg(feature = "tracing")]
+pub mod event_tracer;
+
 pub mod interpolator;
 pub use interpolator::Interpolator;
 
+pub mod multi_picture;
+
+mod n_way_canvas;
+pub use n_way_canvas::*;
+
 mod null_canvas;
 pub use null_canvas::*;
 
+mod overdraw_canvas;
+pub use overdraw_canvas::*;
+
+mod paint_filter_canvas;
+pub use paint_filter_canvas::*;
+
 pub mod parse_path;
 pub mod shadow_utils;
 pub mod text_utils;
@@ -0,0 +1,653 @@
+use crate::canvas::{PointMode, QuadAAFlags, SaveLayerFlags, SrcRectConstraint};
+use crate::prelude::*;
+use crate::utils::shadow_utils::ShadowFlags;
+use crate::{
+    BlendMode, Canvas, ClipOp, Color, Color4f, Data, Drawable, IRect, ISize, Image, Matrix,
+    OwnedCanvas, Paint, Path, Picture, Point, Point3, RRect, RSXform, Rect, Region, Scalar,
+    TextBlob, Vertices,
+};
+use skia_bindings as sb;
+use skia_bindings::{RustDrawCommand, RustDrawCommandKind};
+use std::ffi::{c_void, CStr};
+use std::slice;
+
+/// A drawing command received by a `DrawCommandCanvas`.
+///
+/// Paints, paths, and all other arguments are copied or reference counted, so commands can be
+/// stored and compared after the drawing call returned.
+#[derive(Clone)]
+pub enum DrawCommand {
+    Save,
+    SaveLayer {
+        bounds: Option<Rect>,
+        paint: Option<Paint>,
+        flags: SaveLayerFlags,
+    },
+    Restore,
+    Concat(Matrix),
+    SetMatrix(Matrix),
+    ClipRect {
+        rect: Rect,
+        op: ClipOp,
+        do_anti_alias: bool,
+    },
+    ClipRRect {
+        rrect: RRect,
+        op: ClipOp,
+        do_anti_alias: bool,
+    },
+    ClipPath {
+        path: Path,
+        op: ClipOp,
+        do_anti_alias: bool,
+    },
+    ClipRegion {
+        region: Region,
+        op: ClipOp,
+    },
+    DrawPaint(Paint),
+    DrawBehind(Paint),
+    DrawRect {
+        rect: Rect,
+        paint: Paint,
+    },
+    DrawRRect {
+        rrect: RRect,
+        paint: Paint,
+    },
+    DrawDRRect {
+        outer: RRect,
+        inner: RRect,
+        paint: Paint,
+    },
+    DrawOval {
+        oval: Rect,
+        paint: Paint,
+    },
+    DrawArc {
+        oval: Rect,
+        start_angle: Scalar,
+        sweep_angle: Scalar,
+        use_center: bool,
+        paint: Paint,
+    },
+    DrawPath {
+        path: Path,
+        paint: Paint,
+    },
+    DrawRegion {
+        region: Region,
+        paint: Paint,
+    },
+    DrawTextBlob {
+        blob: TextBlob,
+        origin: Point,
+        paint: Paint,
+    },
+    DrawPoints {
+        mode: PointMode,
+        points: Vec<Point>,
+        paint: Paint,
+    },
+    DrawVertices {
+        vertices: Vertices,
+        mode: BlendMode,
+        paint: Paint,
+    },
+    DrawPatch {
+        cubics: Vec<Point>,
+        colors: Option<[Color; 4]>,
+        tex_coords: Option<[Point; 4]>,
+        mode: BlendMode,
+        paint: Paint,
+    },
+    DrawAtlas {
+        atlas: Image,
+        xforms: Vec<RSXform>,
+        tex: Vec<Rect>,
+        colors: Option<Vec<Color>>,
+        mode: BlendMode,
+        cull: Option<Rect>,
+        paint: Option<Paint>,
+    },
+    DrawShadow {
+        path: Path,
+        rec: draw_command::ShadowRec,
+    },
+    DrawImage {
+        image: Image,
+        left_top: Point,
+        paint: Option<Paint>,
+    },
+    DrawImageRect {
+        image: Image,
+        src: Option<Rect>,
+        dst: Rect,
+        paint: Option<Paint>,
+        constraint: SrcRectConstraint,
+    },
+    DrawImageNine {
+        image: Image,
+        center: IRect,
+        dst: Rect,
+        paint: Option<Paint>,
+    },
+    DrawImageLattice {
+        image: Image,
+        lattice: draw_command::Lattice,
+        dst: Rect,
+        paint: Option<Paint>,
+    },
+    DrawEdgeAAQuad {
+        rect: Rect,
+        clip: Vec<Point>,
+        aa_flags: QuadAAFlags,
+        color: Color4f,
+        mode: BlendMode,
+    },
+    DrawEdgeAAImageSet {
+        entries: Vec<draw_command::ImageSetEntry>,
+        dst_clips: Vec<Point>,
+        pre_view_matrices: Vec<Matrix>,
+        paint: Option<Paint>,
+        constraint: SrcRectConstraint,
+    },
+    DrawAnnotation {
+        rect: Rect,
+        key: String,
+        value: Option<Data>,
+    },
+    DrawPicture {
+        picture: Picture,
+        matrix: Option<Matrix>,
+        paint: Option<Paint>,
+    },
+    DrawDrawable {
+        drawable: Drawable,
+        matrix: Option<Matrix>,
+    },
+}
+
+pub mod draw_command {
+    use crate::canvas::lattice::RectType;
+    use crate::canvas::QuadAAFlags;
+    use crate::utils::shadow_utils::ShadowFlags;
+    use crate::{scalar, Color, IRect, Image, Point3, Rect};
+
+    /// The parameters of a shadow, see `shadow_utils::draw_shadow()`.
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    pub struct ShadowRec {
+        pub z_plane_params: Point3,
+        pub light_pos: Point3,
+        pub light_radius: scalar,
+        pub ambient_color: Color,
+        pub spot_color: Color,
+        pub flags: ShadowFlags,
+    }
+
+    /// An owned copy of a `canvas::Lattice`.
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct Lattice {
+        pub x_divs: Vec<i32>,
+        pub y_divs: Vec<i32>,
+        pub rect_types: Option<Vec<RectType>>,
+        pub bounds: Option<IRect>,
+        pub colors: Option<Vec<Color>>,
+    }
+
+    /// An entry of `DrawCommand::DrawEdgeAAImageSet`.
+    #[derive(Clone)]
+    pub struct ImageSetEntry {
+        pub image: Image,
+        pub src_rect: Rect,
+        pub dst_rect: Rect,
+        /// The index into `pre_view_matrices`, if the entry is transformed.
+        pub matrix_index: Option<usize>,
+        pub alpha: f32,
+        pub aa_flags: QuadAAFlags,
+        /// If set, the next four points of `dst_clips` clip the entry.
+        pub has_clip: bool,
+    }
+}
+
+impl DrawCommand {
+    unsafe fn from_native(cmd: &RustDrawCommand) -> DrawCommand {
+        use DrawCommand::*;
+
+        let paint = || Paint::from_native_ref(&*cmd.paint).clone();
+        let opt_paint = || {
+            cmd.paint
+                .as_ref()
+                .map(|p| Paint::from_native_ref(p).clone())
+        };
+        let rect = || *Rect::from_native_ref(&*cmd.rect);
+        let opt_rect = || cmd.rect.as_ref().map(|r| *Rect::from_native_ref(r));
+        let rect2 = || *Rect::from_native_ref(&*cmd.rect2);
+        let rrect = || *RRect::from_native_ref(&*cmd.rrect);
+        let matrix = || *Matrix::from_native_ref(&*cmd.matrix);
+        let opt_matrix = || cmd.matrix.as_ref().map(|m| *Matrix::from_native_ref(m));
+        let path = || Path::from_native_ref(&*cmd.path).clone();
+        let region = || Region::from_native_ref(&*cmd.region).clone();
+        let image = || Image::from_unshared_ptr(cmd.image as *mut _).unwrap();
+        let points = || to_vec(cmd.points, cmd.count, |p| *Point::from_native_ref(p));
+        let points2 = || to_vec(cmd.points2, cmd.count2, |p| *Point::from_native_ref(p));
+        let colors = |count| {
+            (!cmd.colors.is_null())
+                .if_true_then_some(|| to_vec(cmd.colors, count, |c| Color::from_native(*c)))
+        };
+
+        match cmd.kind {
+            RustDrawCommandKind::Save => Save,
+            RustDrawCommandKind::SaveLayer => SaveLayer {
+                bounds: opt_rect(),
+                paint: opt_paint(),
+                flags: SaveLayerFlags::from_bits_truncate(cmd.saveLayerFlags),
+            },
+            RustDrawCommandKind::Restore => Restore,
+            RustDrawCommandKind::Concat => Concat(matrix()),
+            RustDrawCommandKind::SetMatrix => SetMatrix(matrix()),
+            RustDrawCommandKind::ClipRect => ClipRect {
+                rect: rect(),
+                op: cmd.clipOp,
+                do_anti_alias: cmd.flag,
+            },
+            RustDrawCommandKind::ClipRRect => ClipRRect {
+                rrect: rrect(),
+                op: cmd.clipOp,
+                do_anti_alias: cmd.flag,
+            },
+            RustDrawCommandKind::ClipPath => ClipPath {
+                path: path(),
+                op: cmd.clipOp,
+                do_anti_alias: cmd.flag,
+            },
+            RustDrawCommandKind::ClipRegion => ClipRegion {
+                region: region(),
+                op: cmd.clipOp,
+            },
+            RustDrawCommandKind::DrawPaint => DrawPaint(paint()),
+            RustDrawCommandKind::DrawBehind => DrawBehind(paint()),
+            RustDrawCommandKind::DrawRect => DrawRect {
+                rect: rect(),
+                paint: paint(),
+            },
+            RustDrawCommandKind::DrawRRect => DrawRRect {
+                rrect: rrect(),
+                paint: paint(),
+            },
+            RustDrawCommandKind::DrawDRRect => DrawDRRect {
+                outer: rrect(),
+                inner: *RRect::from_native_ref(&*cmd.rrect2),
+                paint: paint(),
+            },
+            RustDrawCommandKind::DrawOval => DrawOval {
+                oval: rect(),
+                paint: paint(),
+            },
+            RustDrawCommandKind::DrawArc => DrawArc {
+                oval: rect(),
+                start_angle: cmd.x,
+                sweep_angle: cmd.y,
+                use_center: cmd.flag,
+                paint: paint(),
+            },
+            RustDrawCommandKind::DrawPath => DrawPath {
+                path: path(),
+                paint: paint(),
+            },
+            RustDrawCommandKind::DrawRegion => DrawRegion {
+                region: region(),
+                paint: paint(),
+            },
+            RustDrawCommandKind::DrawTextBlob => DrawTextBlob {
+                blob: TextBlob::from_unshared_ptr(cmd.textBlob as *mut _).unwrap(),
+                origin: Point::new(cmd.x, cmd.y),
+                paint: paint(),
+            },
+            RustDrawCommandKind::DrawPoints => DrawPoints {
+                mode: cmd.pointMode,
+                points: points(),
+                paint: paint(),
+            },
+            RustDrawCommandKind::DrawVertices => DrawVertices {
+                vertices: Vertices::from_unshared_ptr(cmd.vertices as *mut _).unwrap(),
+                mode: cmd.blendMode,
+                paint: paint(),
+            },
+            RustDrawCommandKind::DrawPatch => DrawPatch {
+                cubics: points(),
+                colors: colors(4).map(|c| [c[0], c[1], c[2], c[3]]),
+                tex_coords: (cmd.count2 == 4).if_true_then_some(|| {
+                    let t = points2();
+                    [t[0], t[1], t[2], t[3]]
+                }),
+                mode: cmd.blendMode,
+                paint: paint(),
+            },
+            RustDrawCommandKind::DrawAtlas => DrawAtlas {
+                atlas: image(),
+                xforms: to_vec(cmd.xforms, cmd.count, |x| *RSXform::from_native_ref(x)),
+                tex: to_vec(cmd.rects, cmd.count, |r| *Rect::from_native_ref(r)),
+                colors: colors(cmd.count),
+                mode: cmd.blendMode,
+                cull: opt_rect(),
+                paint: opt_paint(),
+            },
+            RustDrawCommandKind::DrawShadow => {
+                let rec = &*cmd.shadowRec;
+                DrawShadow {
+                    path: path(),
+                    rec: draw_command::ShadowRec {
+                        z_plane_params: *Point3::from_native_ref(&rec.fZPlaneParams),
+                        light_pos: *Point3::from_native_ref(&rec.fLightPos),
+                        light_radius: rec.fLightRadius,
+                        ambient_color: Color::from_native(rec.fAmbientColor),
+                        spot_color: Color::from_native(rec.fSpotColor),
+                        flags: ShadowFlags::from_bits_truncate(rec.fFlags),
+                    },
+                }
+            }
+            RustDrawCommandKind::DrawImage => DrawImage {
+                image: image(),
+                left_top: Point::new(cmd.x, cmd.y),
+                paint: opt_paint(),
+            },
+            RustDrawCommandKind::DrawImageRect => DrawImageRect {
+                image: image(),
+                src: opt_rect(),
+                dst: rect2(),
+                paint: opt_paint(),
+                constraint: cmd.constraint,
+            },
+            RustDrawCommandKind::DrawImageNine => DrawImageNine {
+                image: image(),
+                center: *IRect::from_native_ref(&*cmd.irect),
+                dst: rect2(),
+                paint: opt_paint(),
+            },
+            RustDrawCommandKind::DrawImageLattice => DrawImageLattice {
+                image: image(),
+                lattice: {
+                    let lattice = &*cmd.lattice;
+                    let x_count = lattice.fXCount as usize;
+                    let y_count = lattice.fYCount as usize;
+                    let rect_count = (x_count + 1) * (y_count + 1);
+                    draw_command::Lattice {
+                        x_divs: to_vec(lattice.fXDivs, x_count, |d| *d),
+                        y_divs: to_vec(lattice.fYDivs, y_count, |d| *d),
+                        rect_types: (!lattice.fRectTypes.is_null())
+                            .if_true_then_some(|| to_vec(lattice.fRectTypes, rect_count, |t| *t)),
+                        bounds: lattice.fBounds.as_ref().map(|b| *IRect::from_native_ref(b)),
+                        colors: (!lattice.fColors.is_null()).if_true_then_some(|| {
+                            to_vec(lattice.fColors, rect_count, |c| Color::from_native(*c))
+                        }),
+                    }
+                },
+                dst: rect2(),
+                paint: opt_paint(),
+            },
+            RustDrawCommandKind::DrawEdgeAAQuad => DrawEdgeAAQuad {
+                rect: rect(),
+                clip: points(),
+                aa_flags: QuadAAFlags::from_bits_truncate(cmd.aaFlags),
+                color: Color4f::from_native(cmd.color),
+                mode: cmd.blendMode,
+            },
+            RustDrawCommandKind::DrawEdgeAAImageSet => DrawEdgeAAImageSet {
+                entries: to_vec(cmd.imageSet, cmd.count, |e| draw_command::ImageSetEntry {
+                    image: Image::from_unshared_ptr(e.image as *mut _).unwrap(),
+                    src_rect: *Rect::from_native_ref(&e.srcRect),
+                    dst_rect: *Rect::from_native_ref(&e.dstRect),
+                    matrix_index: e.matrixIndex.try_into().ok(),
+                    alpha: e.alpha,
+                    aa_flags: QuadAAFlags::from_bits_truncate(e.aaFlags),
+                    has_clip: e.hasClip,
+                }),
+                dst_clips: points2(),
+                pre_view_matrices: to_vec(cmd.matrices, cmd.matrixCount, |m| {
+                    *Matrix::from_native_ref(m)
+                }),
+                paint: opt_paint(),
+                constraint: cmd.constraint,
+            },
+            RustDrawCommandKind::DrawAnnotation => DrawAnnotation {
+                rect: rect(),
+                key: CStr::from_ptr(cmd.key).to_string_lossy().into_owned(),
+                value: Data::from_unshared_ptr(cmd.data as *mut _),
+            },
+            RustDrawCommandKind::DrawPicture => DrawPicture {
+                picture: Picture::from_unshared_ptr(cmd.picture as *mut _).unwrap(),
+                matrix: opt_matrix(),
+                paint: opt_paint(),
+            },
+            RustDrawCommandKind::DrawDrawable => DrawDrawable {
+                drawable: Drawable::from_unshared_ptr(cmd.drawable).unwrap(),
+                matrix: opt_matrix(),
+            },
+        }
+    }
+}
+
+/// A canvas that does not draw anything, but reports every drawing, clip, and matrix command
+/// to a closure.
+///
+/// Note that `save()` calls are only reported if they are followed by a matrix or clip
+/// change. If they are not, the matching `restore()` is not reported either.
+pub struct DrawCommandCanvas<'a> {
+    // dropped first, the native canvas points to the inner box.
+    canvas: OwnedCanvas<'a>,
+    _on_command: Box<Box<dyn FnMut(DrawCommand) + 'a>>,
+}
+
+canvas_wrapper!(DrawCommandCanvas);
+
+impl<'a> DrawCommandCanvas<'a> {
+    pub fn new(
+        size: impl Into<ISize>,
+        on_command: impl FnMut(DrawCommand) + 'a,
+    ) -> DrawCommandCanvas<'a> {
+        let size = size.into();
+        let mut on_command: Box<Box<dyn FnMut(DrawCommand) + 'a>> = Box::new(Box::new(on_command));
+        let canvas = Canvas::own_from_native_ptr(unsafe {
+            sb::C_RustDrawCommandCanvas_new(
+                size.width,
+                size.height,
+                on_command.as_mut() as *mut Box<dyn FnMut(DrawCommand) + 'a> as *mut c_void,
+                Some(draw_command),
+            )
+        })
+        .unwrap();
+        DrawCommandCanvas {
+            canvas,
+            _on_command: on_command,
+        }
+    }
+}
+
+/// Maps `count` native values at `ptr` to a `Vec`, `ptr` may be null if `count` is zero.
+unsafe fn to_vec<N, T>(ptr: *const N, count: usize, f: impl FnMut(&N) -> T) -> Vec<T> {
+    if ptr.is_null() {
+        Vec::new()
+    } else {
+        slice::from_raw_parts(ptr, count).iter().map(f).collect()
+    }
+}
+
+unsafe extern "C" fn draw_command(ctx: *mut c_void, cmd: *const RustDrawCommand) {
+    let on_command = &mut *(ctx as *mut Box<dyn FnMut(DrawCommand)>);
+    on_command(DrawCommand::from_native(&*cmd))
+}
+
+impl Picture {
+    /// Plays back the picture into a `DrawCommandCanvas` and returns all the commands received.
+    ///
+    /// Nested pictures with only a few commands are unrolled by the playback.
+    pub fn draw_commands(&self) -> Vec<DrawCommand> {
+        let bounds = self.cull_rect().round_out();
+        let mut commands = Vec::new();
+        {
+            let mut canvas =
+                DrawCommandCanvas::new((bounds.right.max(0), bounds.bottom.max(0)), |cmd| {
+                    commands.push(cmd)
+                });
+            self.playback(&mut canvas);
+        }
+        commands
+    }
+}
+
+#[test]
+fn test_draw_commands() {
+    use crate::{Color, PictureRecorder};
+
+    let mut recorder = PictureRecorder::new();
+    let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 100.0, 100.0), None, None);
+    let paint = Paint::default();
+    canvas.save();
+    canvas.translate((10.0, 10.0));
+    canvas.clip_rect(Rect::new(0.0, 0.0, 50.0, 50.0), None, true);
+    canvas.draw_rect(Rect::new(1.0, 2.0, 3.0, 4.0), &paint);
+    canvas.draw_path(Path::new().add_circle((5.0, 5.0), 5.0, None), &paint);
+    canvas.restore();
+    canvas.draw_color(Color::WHITE, None);
+    let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+    let commands = picture.draw_commands();
+    assert_eq!(commands.len(), 7);
+    assert!(match commands[0] {
+        DrawCommand::Save => true,
+        _ => false,
+    });
+    match commands[1] {
+        DrawCommand::Concat(matrix) => assert_eq!(matrix, Matrix::new_trans((10.0, 10.0))),
+        _ => panic!("expected Concat"),
+    }
+    match commands[2] {
+        DrawCommand::ClipRect {
+            rect,
+            op,
+            do_anti_alias,
+        } => {
+            assert_eq!(rect, Rect::new(0.0, 0.0, 50.0, 50.0));
+            assert!(op == ClipOp::Intersect);
+            assert!(do_anti_alias);
+        }
+        _ => panic!("expected ClipRect"),
+    }
+    match &commands[3] {
+        DrawCommand::DrawRect { rect, paint: p } => {
+            assert_eq!(*rect, Rect::new(1.0, 2.0, 3.0, 4.0));
+            assert!(*p == paint);
+        }
+        _ => panic!("expected DrawRect"),
+    }
+    assert!(match commands[4] {
+        DrawCommand::DrawPath { .. } => true,
+        _ => false,
+    });
+    assert!(match commands[5] {
+        DrawCommand::Restore => true,
+        _ => false,
+    });
+    assert!(match commands[6] {
+        DrawCommand::DrawPaint(_) => true,
+        _ => false,
+    });
+}
+
+#[test]
+fn test_draw_commands_carry_their_arguments() {
+    use crate::canvas::{lattice::RectType, Lattice};
+    use crate::utils::shadow_utils::ShadowFlags;
+    use crate::{Color, PictureRecorder, Surface};
+
+    let mut surface = Surface::new_raster_n32_premul((8, 8)).unwrap();
+    let image = surface.image_snapshot();
+
+    let mut recorder = PictureRecorder::new();
+    let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 100.0, 100.0), None, None);
+    let paint = Paint::default();
+    let cubics = [
+        Point::new(0.0, 0.0),
+        Point::new(10.0, 0.0),
+        Point::new(20.0, 0.0),
+        Point::new(30.0, 0.0),
+        Point::new(30.0, 10.0),
+        Point::new(30.0, 20.0),
+        Point::new(30.0, 30.0),
+        Point::new(20.0, 30.0),
+        Point::new(10.0, 30.0),
+        Point::new(0.0, 30.0),
+        Point::new(0.0, 20.0),
+        Point::new(0.0, 10.0),
+    ];
+    let colors = [Color::RED, Color::GREEN, Color::BLUE, Color::WHITE];
+    let tex_coords = [
+        Point::new(0.0, 0.0),
+        Point::new(8.0, 0.0),
+        Point::new(8.0, 8.0),
+        Point::new(0.0, 8.0),
+    ];
+    canvas.draw_patch(&cubics, &colors, &tex_coords, BlendMode::Modulate, &paint);
+    canvas.draw_shadow(
+        Path::new().add_rect(Rect::new(10.0, 10.0, 20.0, 20.0), None),
+        (0.0, 0.0, 4.0),
+        (50.0, 0.0, 600.0),
+        800.0,
+        Color::BLACK,
+        Color::BLUE,
+        ShadowFlags::TRANSPARENT_OCCLUDER,
+    );
+    let rect_types = [RectType::Default; 4];
+    let lattice_colors = [Color::TRANSPARENT; 4];
+    canvas.draw_image_lattice(
+        &image,
+        &Lattice {
+            x_divs: &[2],
+            y_divs: &[6],
+            rect_types: Some(&rect_types),
+            bounds: None,
+            colors: Some(&lattice_colors),
+        },
+        Rect::new(0.0, 0.0, 40.0, 40.0),
+        None,
+    );
+    let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+    let commands = picture.draw_commands();
+    assert_eq!(commands.len(), 3);
+    match &commands[0] {
+        DrawCommand::DrawPatch {
+            cubics: c,
+            colors: cs,
+            tex_coords: t,
+            mode,
+            ..
+        } => {
+            assert_eq!(c[..], cubics[..]);
+            assert_eq!(*cs, Some(colors));
+            assert_eq!(*t, Some(tex_coords));
+            assert!(*mode == BlendMode::Modulate);
+        }
+        _ => panic!("expected DrawPatch"),
+    }
+    match &commands[1] {
+        DrawCommand::DrawShadow { rec, .. } => {
+            assert_eq!(rec.light_pos, Point3::from((50.0, 0.0, 600.0)));
+            assert_eq!(rec.light_radius, 800.0);
+            assert_eq!(rec.spot_color, Color::BLUE);
+            assert!(rec.flags.contains(ShadowFlags::TRANSPARENT_OCCLUDER));
+        }
+        _ => panic!("expected DrawShadow"),
+    }
+    match &commands[2] {
+        DrawCommand::DrawImageLattice { lattice, dst, .. } => {
+            assert_eq!(lattice.x_divs, vec![2]);
+            assert_eq!(lattice.y_divs, vec![6]);
+            assert_eq!(lattice.rect_types.as_ref().unwrap().len(), 4);
+            assert_eq!(lattice.colors.as_ref().unwrap().len(), 4);
+            assert_eq!(*dst, Rect::new(0.0, 0.0, 40.0, 40.0));
+        }
+        _ => panic!("expected DrawImageLattice"),
+    }
+}
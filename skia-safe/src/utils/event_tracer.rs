@@ -0,0 +1,78 @@
+//! Forwards Skia's trace events to the `tracing` crate.
+
+use skia_bindings as sb;
+use skia_bindings::RustEventTracerParam;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::os::raw;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing_crate as tracing;
+use tracing_crate::span::EnteredSpan;
+
+/// Installs an event tracer that forwards Skia's trace events to `tracing`.
+///
+/// Scoped trace events become spans named `skia` at the `TRACE` level, and instant events
+/// become events, both with the target `skia` and the fields `category` and `name`.
+///
+/// Returns `false` if an event tracer was already installed.
+pub fn install() -> bool {
+    let param = RustEventTracerParam {
+        isCategoryEnabled: Some(is_category_enabled),
+        begin: Some(begin),
+        end: Some(end),
+        instant: Some(instant),
+    };
+    unsafe { sb::C_SkEventTracer_SetRustInstance(&param) }
+}
+
+// Skia asks only once per category, so the decision is left to the subscribers that are active
+// when the events are recorded.
+unsafe extern "C" fn is_category_enabled(_category: *const raw::c_char) -> bool {
+    tracing::level_filters::STATIC_MAX_LEVEL >= tracing::Level::TRACE
+}
+
+thread_local! {
+    // Skia's scoped events begin and end on the same thread in LIFO order, which is also how
+    // entered spans must be exited.
+    static ENTERED_SPANS: RefCell<Vec<(u64, EnteredSpan)>> = RefCell::new(Vec::new());
+}
+
+static NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
+
+unsafe extern "C" fn begin(category: *const raw::c_char, name: *const raw::c_char) -> u64 {
+    let span = tracing::trace_span!(
+        target: "skia",
+        "skia",
+        category = &*to_str(category),
+        name = &*to_str(name)
+    )
+    .entered();
+    let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+    ENTERED_SPANS.with(|spans| spans.borrow_mut().push((handle, span)));
+    handle
+}
+
+unsafe extern "C" fn end(handle: u64) {
+    let (top, span) = ENTERED_SPANS
+        .with(|spans| spans.borrow_mut().pop())
+        .expect("Skia ended a trace event that was not begun on this thread");
+    assert_eq!(
+        top, handle,
+        "Skia ended trace events out of order or on a different thread"
+    );
+    // exits the span outside of the borrow, subscribers may record events.
+    drop(span);
+}
+
+unsafe extern "C" fn instant(category: *const raw::c_char, name: *const raw::c_char) {
+    tracing::trace!(
+        target: "skia",
+        category = &*to_str(category),
+        name = &*to_str(name)
+    );
+}
+
+unsafe fn to_str<'a>(str: *const raw::c_char) -> Cow<'a, str> {
+    CStr::from_ptr(str).to_string_lossy()
+}
@@ -0,0 +1,149 @@
+use crate::interop::DynamicMemoryWStream;
+use crate::prelude::*;
+use crate::{DeserialProcs, Document, OwnedSerialProcs, Picture, SerialProcs, Size};
+use skia_bindings as sb;
+use skia_bindings::{RustDeserialProcs, SkPicture, SkSize};
+use std::ptr;
+
+/// A page that was read from a multi-picture document.
+#[derive(Clone)]
+pub struct Page {
+    pub picture: Picture,
+    pub size: Size,
+}
+
+/// Creates a document that records every page into a picture and encodes all of them into
+/// a single stream, usually stored with the `.mskp` extension.
+pub fn new_document() -> Document {
+    new_document_with_serial_procs(None)
+}
+
+/// Creates a multi-picture document that uses `procs` to serialize the pictures, images, and
+/// typefaces of its pages when it is closed.
+///
+/// Note that the picture closure is also called for the picture of every page.
+pub fn new_document_with_procs(procs: SerialProcs<'static>) -> Document {
+    new_document_with_serial_procs(Some(OwnedSerialProcs::new(procs)))
+}
+
+fn new_document_with_serial_procs(procs: Option<OwnedSerialProcs>) -> Document {
+    // we can't move the memory stream around anymore as soon it's referred by
+    // the document.
+    let mut memory_stream = Box::pin(DynamicMemoryWStream::new());
+    let native_procs = procs
+        .as_ref()
+        .map(|p| p.native() as *const _)
+        .unwrap_or(ptr::null());
+    let document = RCHandle::from_ptr(unsafe {
+        sb::C_SkMultiPictureDocument_Make(memory_stream.native_mut().base_mut(), native_procs)
+    })
+    .unwrap();
+
+    Document::new_with_serial_procs(memory_stream, document, procs)
+}
+
+/// Returns the number of pages of an encoded multi-picture document, or `None` if the
+/// data is not a multi-picture document.
+pub fn page_count(data: &[u8]) -> Option<usize> {
+    let count =
+        unsafe { sb::C_SkMultiPictureDocument_ReadPageCount(data.as_ptr() as _, data.len()) };
+    if count > 0 {
+        Some(count.try_into().unwrap())
+    } else {
+        None
+    }
+}
+
+/// Reads all pages of an encoded multi-picture document.
+pub fn read(data: &[u8]) -> Option<Vec<Page>> {
+    read_pages(data, None)
+}
+
+/// Reads all pages of an encoded multi-picture document and uses `procs` to deserialize
+/// the pictures, images, and typefaces they contain.
+pub fn read_with_procs(data: &[u8], procs: &mut DeserialProcs) -> Option<Vec<Page>> {
+    let native_procs = procs.native_procs();
+    read_pages(data, Some(&native_procs))
+}
+
+fn read_pages(data: &[u8], procs: Option<&RustDeserialProcs>) -> Option<Vec<Page>> {
+    let count = page_count(data)?;
+    let mut pictures: Vec<*mut SkPicture> = vec![ptr::null_mut(); count];
+    let mut sizes = vec![Size::default(); count];
+    let procs = procs.map(|p| p as *const _).unwrap_or(ptr::null());
+    if !unsafe {
+        sb::C_SkMultiPictureDocument_Read(
+            data.as_ptr() as _,
+            data.len(),
+            pictures.as_mut_ptr(),
+            sizes.as_mut_ptr() as *mut SkSize,
+            count.try_into().unwrap(),
+            procs,
+        )
+    } {
+        return None;
+    }
+    // take ownership of all pictures before deciding if a page is missing.
+    let pictures: Vec<Option<Picture>> = pictures.into_iter().map(Picture::from_ptr).collect();
+    pictures
+        .into_iter()
+        .zip(sizes)
+        .map(|(picture, size)| picture.map(|picture| Page { picture, size }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::multi_picture;
+    use crate::{Color, Paint, Rect, SerialProcs, Size};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn pages_round_trip() {
+        let mut document = multi_picture::new_document();
+        for (i, size) in [(100, 200), (300, 150)].iter().enumerate() {
+            let mut page = document.begin_page(*size, None);
+            let mut paint = Paint::default();
+            paint.set_color(if i == 0 { Color::RED } else { Color::BLUE });
+            page.canvas()
+                .draw_rect(Rect::new(10.0, 10.0, 50.0, 50.0), &paint);
+            document = page.end_page();
+        }
+        let data = document.close();
+
+        assert_eq!(multi_picture::page_count(&data), Some(2));
+        let pages = multi_picture::read(&data).unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].size, Size::new(100.0, 200.0));
+        assert_eq!(pages[1].size, Size::new(300.0, 150.0));
+        assert!(pages[0].picture.approximate_op_count() > 0);
+
+        assert!(multi_picture::read(b"not a document").is_none());
+    }
+
+    #[test]
+    fn pages_are_serialized_with_procs() {
+        let pictures = Arc::new(AtomicUsize::new(0));
+        let mut procs = SerialProcs::default();
+        {
+            let pictures = pictures.clone();
+            procs.set_picture_proc(move |_| {
+                pictures.fetch_add(1, Ordering::SeqCst);
+                None
+            });
+        }
+
+        let mut document = multi_picture::new_document_with_procs(procs);
+        for _ in 0..2 {
+            let mut page = document.begin_page((100, 100), None);
+            page.canvas()
+                .draw_rect(Rect::new(10.0, 10.0, 50.0, 50.0), &Paint::default());
+            document = page.end_page();
+        }
+        let data = document.close();
+
+        assert_eq!(pictures.load(Ordering::SeqCst), 2);
+        assert_eq!(multi_picture::read(&data).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::prelude::*;
+use crate::{Canvas, ISize, OwnedCanvas};
+use skia_bindings as sb;
+
+/// A canvas that forwards all drawing, matrix, and clip commands to a number of other canvases.
+pub struct NWayCanvas<'a> {
+    canvas: OwnedCanvas<'a>,
+}
+
+canvas_wrapper!(NWayCanvas);
+
+impl<'a> NWayCanvas<'a> {
+    /// Creates a canvas without targets. `size` limits the initial clip.
+    pub fn new(size: impl Into<ISize>) -> NWayCanvas<'a> {
+        let size = size.into();
+        let canvas =
+            Canvas::own_from_native_ptr(unsafe { sb::C_SkNWayCanvas_new(size.width, size.height) })
+                .unwrap();
+        NWayCanvas { canvas }
+    }
+
+    /// Adds a canvas that receives all subsequent commands. Its current matrix and clip are
+    /// kept, so they should match the ones of the `NWayCanvas`.
+    pub fn add_canvas(&mut self, canvas: &'a mut Canvas) -> &mut Self {
+        unsafe { sb::C_SkNWayCanvas_addCanvas(self.canvas.native_mut(), canvas.native_mut()) }
+        self
+    }
+
+    /// Removes all target canvases.
+    pub fn remove_all(&mut self) -> &mut Self {
+        unsafe { sb::C_SkNWayCanvas_removeAll(self.canvas.native_mut()) }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NWayCanvas;
+    use crate::{Color, Paint, Rect, Surface};
+
+    #[test]
+    fn draws_are_forwarded_to_all_targets() {
+        let mut first = Surface::new_raster_n32_premul((8, 8)).unwrap();
+        let mut second = Surface::new_raster_n32_premul((8, 8)).unwrap();
+        {
+            let mut canvas = NWayCanvas::new((8, 8));
+            canvas
+                .add_canvas(first.canvas())
+                .add_canvas(second.canvas());
+            canvas.clear(Color::WHITE);
+            let mut paint = Paint::default();
+            paint.set_color(Color::RED);
+            canvas.draw_rect(Rect::new(0.0, 0.0, 4.0, 8.0), &paint);
+        }
+        for surface in &mut [first, second] {
+            let pixmap = surface.peek_pixels().unwrap();
+            assert_eq!(pixmap.get_color((2, 4)), Color::RED);
+            assert_eq!(pixmap.get_color((6, 4)), Color::WHITE);
+        }
+    }
+}
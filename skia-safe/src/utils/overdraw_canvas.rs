@@ -0,0 +1,41 @@
+use crate::prelude::*;
+use crate::{Canvas, OwnedCanvas};
+use skia_bindings as sb;
+
+/// A canvas that does not draw, but counts how often each pixel of its target is drawn to by
+/// incrementing the target's alpha value.
+///
+/// The target is usually the canvas of an alpha-only surface. Its contents can be turned into
+/// a heat map by drawing them with `overdraw_color_filter::new()`.
+pub struct OverdrawCanvas<'a> {
+    canvas: OwnedCanvas<'a>,
+}
+
+canvas_wrapper!(OverdrawCanvas);
+
+impl<'a> OverdrawCanvas<'a> {
+    /// Creates a canvas that counts the overdraw in `target`.
+    pub fn new(target: &'a mut Canvas) -> OverdrawCanvas<'a> {
+        let canvas =
+            Canvas::own_from_native_ptr(unsafe { sb::C_SkOverdrawCanvas_new(target.native_mut()) })
+                .unwrap();
+        OverdrawCanvas { canvas }
+    }
+}
+
+#[test]
+fn overdraw_is_counted_in_alpha() {
+    use crate::{ImageInfo, Paint, Rect, Surface};
+
+    let mut surface = Surface::new_raster(&ImageInfo::new_a8((4, 4)), None, None).unwrap();
+    {
+        let mut canvas = OverdrawCanvas::new(surface.canvas());
+        let paint = Paint::default();
+        canvas.draw_rect(Rect::new(0.0, 0.0, 4.0, 4.0), &paint);
+        canvas.draw_rect(Rect::new(0.0, 0.0, 4.0, 4.0), &paint);
+        canvas.draw_rect(Rect::new(0.0, 0.0, 2.0, 4.0), &paint);
+    }
+    let pixmap = surface.peek_pixels().unwrap();
+    assert_eq!(pixmap.get_color((1, 1)).a(), 3);
+    assert_eq!(pixmap.get_color((3, 1)).a(), 2);
+}
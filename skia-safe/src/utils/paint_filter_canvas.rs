@@ -0,0 +1,102 @@
+use crate::prelude::*;
+use crate::{Canvas, OwnedCanvas, Paint};
+use skia_bindings as sb;
+use skia_bindings::SkPaint;
+use std::ffi::c_void;
+
+pub use skia_bindings::RustPaintFilterDrawType as PaintFilterDrawType;
+#[test]
+fn test_paint_filter_draw_type_naming() {
+    let _ = PaintFilterDrawType::TextBlob;
+}
+
+/// Rewrites the paints of the draw calls that go through a `PaintFilterCanvas`.
+///
+/// Draw calls without a paint, like `draw_image()` with `None`, are filtered with a default
+/// paint. Paints inside of text blobs or nested pictures are not filtered, the paint the
+/// picture is drawn with is.
+pub trait PaintFilter {
+    /// Modifies `paint` before it is used to draw `draw_type`. Returns `false` to skip the draw.
+    fn filter(&mut self, paint: &mut Paint, draw_type: PaintFilterDrawType) -> bool;
+}
+
+impl<F> PaintFilter for F
+where
+    F: FnMut(&mut Paint, PaintFilterDrawType) -> bool,
+{
+    fn filter(&mut self, paint: &mut Paint, draw_type: PaintFilterDrawType) -> bool {
+        self(paint, draw_type)
+    }
+}
+
+/// A canvas that forwards all drawing to another canvas after its paints were passed through a
+/// `PaintFilter`.
+pub struct PaintFilterCanvas<'a> {
+    // dropped first, the native canvas points to the inner box and draws into the target.
+    canvas: OwnedCanvas<'a>,
+    _filter: Box<Box<dyn PaintFilter + 'a>>,
+}
+
+canvas_wrapper!(PaintFilterCanvas);
+
+impl<'a> PaintFilterCanvas<'a> {
+    /// Creates a canvas that draws into `canvas`. It starts with the matrix and clip of
+    /// `canvas` and forwards all matrix and clip changes to it.
+    pub fn new(canvas: &'a mut Canvas, filter: impl PaintFilter + 'a) -> PaintFilterCanvas<'a> {
+        let mut filter: Box<Box<dyn PaintFilter + 'a>> = Box::new(Box::new(filter));
+        let canvas = Canvas::own_from_native_ptr(unsafe {
+            sb::C_RustPaintFilterCanvas_new(
+                canvas.native_mut(),
+                filter.as_mut() as *mut Box<dyn PaintFilter + 'a> as *mut c_void,
+                Some(filter_paint),
+            )
+        })
+        .unwrap();
+        PaintFilterCanvas {
+            canvas,
+            _filter: filter,
+        }
+    }
+}
+
+unsafe extern "C" fn filter_paint(
+    ctx: *mut c_void,
+    paint: *mut SkPaint,
+    draw_type: PaintFilterDrawType,
+) -> bool {
+    let filter = &mut *(ctx as *mut Box<dyn PaintFilter>);
+    filter.filter(Paint::from_native_ref_mut(&mut *paint), draw_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PaintFilterCanvas, PaintFilterDrawType};
+    use crate::{Color, Paint, Rect, Surface};
+
+    #[test]
+    fn paints_are_rewritten_and_draws_skipped() {
+        let mut surface = Surface::new_raster_n32_premul((16, 16)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+
+        let mut filtered = Vec::new();
+        {
+            let mut canvas = PaintFilterCanvas::new(
+                surface.canvas(),
+                |paint: &mut Paint, ty: PaintFilterDrawType| {
+                    filtered.push(ty);
+                    paint.set_color(Color::BLUE);
+                    ty != PaintFilterDrawType::Oval
+                },
+            );
+            let mut paint = Paint::default();
+            paint.set_color(Color::RED);
+            canvas.draw_rect(Rect::new(0.0, 0.0, 8.0, 16.0), &paint);
+            canvas.draw_oval(Rect::new(8.0, 0.0, 16.0, 16.0), &paint);
+        }
+
+        assert!(filtered == [PaintFilterDrawType::Rect, PaintFilterDrawType::Oval]);
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((4, 8)), Color::BLUE);
+        assert_eq!(pixmap.get_color((12, 8)), Color::WHITE);
+    }
+}
@@ -0,0 +1,50 @@
+//! The event tracer is installed process wide, so it is tested in its own test binary.
+#![cfg(feature = "tracing")]
+
+use skia_safe::utils::event_tracer;
+use skia_safe::{Color, Paint, Rect, Surface};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing_crate as tracing;
+use tracing_crate::span::{Attributes, Id, Record};
+use tracing_crate::{Event, Metadata, Subscriber};
+
+struct SkiaEvents(Arc<AtomicUsize>);
+
+impl Subscriber for SkiaEvents {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target() == "skia"
+    }
+
+    fn new_span(&self, _span: &Attributes) -> Id {
+        Id::from_u64(self.0.fetch_add(1, Ordering::SeqCst) as u64 + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn install_once_and_trace_drawing() {
+    assert!(event_tracer::install());
+    assert!(!event_tracer::install());
+
+    let events = Arc::new(AtomicUsize::new(0));
+    tracing::subscriber::with_default(SkiaEvents(events.clone()), || {
+        let mut surface = Surface::new_raster_n32_premul((16, 16)).unwrap();
+        surface
+            .canvas()
+            .draw_rect(Rect::new(0.0, 0.0, 8.0, 8.0), &Paint::default())
+            .clear(Color::WHITE);
+    });
+    assert!(events.load(Ordering::SeqCst) > 0);
+}
@@ -0,0 +1,68 @@
+#!/usr/bin/env python3
+# Writes linear_gradient.dng, see README.md.
+
+import struct
+
+WIDTH = 16
+HEIGHT = 8
+
+pixels = bytes(
+    v for _ in range(HEIGHT) for x in range(WIDTH) for v in [x * 255 // (WIDTH - 1)] * 3
+)
+
+# (tag, type, count, value), the values of entries that do not fit into 4 bytes are stored
+# after the IFD.
+SHORT, LONG, BYTE, ASCII, RATIONAL, SRATIONAL = 3, 4, 1, 2, 5, 10
+entries = [
+    (254, LONG, [0]),  # NewSubfileType
+    (256, LONG, [WIDTH]),  # ImageWidth
+    (257, LONG, [HEIGHT]),  # ImageLength
+    (258, SHORT, [8, 8, 8]),  # BitsPerSample
+    (259, SHORT, [1]),  # Compression: none
+    (262, SHORT, [34892]),  # PhotometricInterpretation: LinearRaw
+    (273, LONG, None),  # StripOffsets, patched below
+    (277, SHORT, [3]),  # SamplesPerPixel
+    (278, LONG, [HEIGHT]),  # RowsPerStrip
+    (279, LONG, [len(pixels)]),  # StripByteCounts
+    (284, SHORT, [1]),  # PlanarConfiguration: chunky
+    (50706, BYTE, [1, 4, 0, 0]),  # DNGVersion
+    (50708, ASCII, b"rust-skia\0"),  # UniqueCameraModel
+    (50721, SRATIONAL, [1, 0, 0, 0, 1, 0, 0, 0, 1]),  # ColorMatrix1: identity
+    (50728, RATIONAL, [1, 1, 1]),  # AsShotNeutral
+    (50778, SHORT, [21]),  # CalibrationIlluminant1: D65
+]
+
+
+def encode(typ, values):
+    if typ == ASCII:
+        return values
+    if typ in (RATIONAL, SRATIONAL):
+        fmt = "<I" if typ == RATIONAL else "<i"
+        return b"".join(struct.pack(fmt, v) + struct.pack(fmt, 1) for v in values)
+    fmt = {BYTE: "<B", SHORT: "<H", LONG: "<I"}[typ]
+    return b"".join(struct.pack(fmt, v) for v in values)
+
+
+ifd_offset = 8
+data_offset = ifd_offset + 2 + len(entries) * 12 + 4
+extra = b""
+ifd = struct.pack("<H", len(entries))
+for tag, typ, values in entries:
+    if values is None:
+        values = [0]
+    payload = encode(typ, values)
+    count = len(values)
+    if len(payload) <= 4:
+        ifd += struct.pack("<HHI", tag, typ, count) + payload.ljust(4, b"\0")
+    else:
+        ifd += struct.pack("<HHII", tag, typ, count, data_offset + len(extra))
+        extra += payload
+ifd += struct.pack("<I", 0)
+
+strip_offset = data_offset + len(extra)
+index = [tag for tag, _, _ in entries].index(273)
+entry_offset = 2 + index * 12 + 8
+ifd = ifd[:entry_offset] + struct.pack("<I", strip_offset) + ifd[entry_offset + 4 :]
+
+with open("linear_gradient.dng", "wb") as f:
+    f.write(b"II*\0" + struct.pack("<I", ifd_offset) + ifd + extra + pixels)